@@ -40,7 +40,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         .iter()
         .map(|va| {
             let v = va * counts_b.get(va).unwrap_or(&0);
-            println!("{}", v);
+            advent_of_code::trace!("{}", v);
             v
         })
         .sum();
@@ -1,40 +1,84 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{parse, AppArguments};
+use std::collections::HashSet;
 
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::{
+    all, completions, dashboard, download, help, read, scaffold, solve, status, templates, time,
+};
+use advent_of_code::template::day_expr::DayExpr;
+use advent_of_code::template::error::{Error, Result};
+use advent_of_code::template::{all_days, Day};
+use args::{parse, AppArguments};
 
 mod args {
+    use advent_of_code::template::commands::completions::Shell;
+    use advent_of_code::template::commands::help::{self, CommandSpec};
+    use advent_of_code::template::day_expr::DayExpr;
+    use advent_of_code::template::noise::{NoiseControl, DEFAULT_ROUNDS};
     use advent_of_code::template::Day;
-    use std::process;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            days: Option<DayExpr>,
+            all: bool,
         },
         Read {
             day: Day,
+            refresh: bool,
+            part: Option<u8>,
         },
         Scaffold {
-            day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            answer_type: Option<String>,
+            day: Day,
         },
+        Templates,
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            threads: Option<usize>,
+            part: Option<u8>,
+            show_output: bool,
+            verbosity: u8,
+            explain: bool,
+            explain_json: Option<String>,
+            profile_spans: bool,
+            watch: bool,
+            example: bool,
         },
         All {
+            days: Option<DayExpr>,
             release: bool,
+            jobs: Option<usize>,
+            threads: Option<usize>,
+            part: Option<u8>,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<DayExpr>,
             store: bool,
+            rev: Option<String>,
+            merge_machines: bool,
+            noise_control: Option<NoiseControl>,
+            threads: Option<usize>,
+            part: Option<u8>,
+        },
+        TimeScaling {
+            day: Day,
+            threads: Option<usize>,
+            part: Option<u8>,
+        },
+        Status {
+            days: Option<DayExpr>,
+        },
+        Dashboard,
+        Help {
+            command: Option<&'static CommandSpec>,
+        },
+        Completions {
+            shell: Shell,
         },
         #[cfg(feature = "today")]
         Today,
@@ -42,103 +86,352 @@ mod args {
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let subcommand = args.subcommand()?;
+        let wants_help = args.contains(["-h", "--help"]);
+
+        let subcommand = match subcommand.as_deref() {
+            None => return Ok(AppArguments::Help { command: None }),
+            Some("help") => {
+                let command = args
+                    .opt_free_from_str::<String>()?
+                    .map(|name| help::find(&name).ok_or(format!("unknown command `{name}`.")))
+                    .transpose()?;
+                return Ok(AppArguments::Help { command });
+            }
+            Some(x) => help::find(x).ok_or(format!(
+                "unknown command `{x}`. Run `cargo run -- help` for a list of commands."
+            ))?,
+        };
+
+        if wants_help {
+            return Ok(AppArguments::Help {
+                command: Some(subcommand),
+            });
+        }
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+        parse_command(subcommand.name, &mut args)
+            .and_then(|app_args| {
+                let remaining = args.finish();
+                if remaining.is_empty() {
+                    Ok(app_args)
+                } else {
+                    let remaining: Vec<_> = remaining.iter().map(|x| x.to_string_lossy()).collect();
+                    Err(format!("unexpected argument(s) `{}`.", remaining.join(" ")).into())
+                }
+            })
+            .map_err(|e| {
+                format!(
+                    "{e}\n\nUsage: {}\nRun `cargo {} --help` for more information.",
+                    help::usage(subcommand),
+                    subcommand.name
+                )
+                .into()
+            })
+    }
+
+    fn parse_command(
+        command: &str,
+        args: &mut pico_args::Arguments,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let app_args = match command {
+            "all" => AppArguments::All {
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?,
+                threads: args.opt_value_from_str("--threads")?,
+                part: args.opt_value_from_str("--part")?,
+                days: args.opt_free_from_str()?,
             },
-            Some("time") => {
+            "time" => {
+                if args.contains("--jobs") {
+                    return Err(
+                        "`--jobs` is not supported by `time`, benchmarks always run serially."
+                            .into(),
+                    );
+                }
+
+                let threads = args.opt_value_from_str("--threads")?;
+                let part = args.opt_value_from_str("--part")?;
+
+                if args.contains("--scaling") {
+                    let mut conflicts = ["--all", "--store", "--merge-machines", "--stable"]
+                        .into_iter()
+                        .filter(|flag| args.contains(*flag))
+                        .collect::<Vec<_>>();
+                    for flag in ["--rev", "--core", "--rounds"] {
+                        if args.opt_value_from_str::<_, String>(flag)?.is_some() {
+                            conflicts.push(flag);
+                        }
+                    }
+
+                    if let Some(flag) = conflicts.first() {
+                        return Err(format!("`{flag}` can not be combined with `--scaling`, scaling runs bench a single day and are never stored.").into());
+                    }
+
+                    let Some(day) = args.opt_free_from_str()? else {
+                        return Err(
+                            "`--scaling` requires a day, e.g. `cargo time 11 --scaling`.".into(),
+                        );
+                    };
+
+                    return Ok(AppArguments::TimeScaling { day, threads, part });
+                }
+
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let rev = args.opt_value_from_str("--rev")?;
+                let merge_machines = args.contains("--merge-machines");
+
+                if store && part.is_some() {
+                    return Err("`--part` can not be combined with `--store`, stored benchmarks always cover all parts.".into());
+                }
+
+                // passing `--core` or `--rounds` implies `--stable`.
+                let stable = args.contains("--stable");
+                let core = args.opt_value_from_str("--core")?;
+                let rounds = args.opt_value_from_str("--rounds")?;
+                let noise_control =
+                    (stable || core.is_some() || rounds.is_some()).then(|| NoiseControl {
+                        core,
+                        rounds: rounds.unwrap_or(DEFAULT_ROUNDS),
+                    });
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    days: args.opt_free_from_str()?,
                     store,
+                    rev,
+                    merge_machines,
+                    noise_control,
+                    threads,
+                    part,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            "download" => {
+                let all = args.contains("--all");
+                let days = args.opt_free_from_str()?;
+
+                if all == days.is_some() {
+                    return Err(
+                        "`download` requires either days or `--all`, e.g. `cargo download 1-5`."
+                            .into(),
+                    );
+                }
+
+                AppArguments::Download { days, all }
+            }
+            "dashboard" => AppArguments::Dashboard,
+            "status" => AppArguments::Status {
+                days: args.opt_free_from_str()?,
             },
-            Some("read") => AppArguments::Read {
+            "read" => AppArguments::Read {
+                refresh: args.contains("--refresh"),
+                part: args.opt_value_from_str("--part")?,
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+            "scaffold" => AppArguments::Scaffold {
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
+                template: args.opt_value_from_str("--template")?,
+                answer_type: args.opt_value_from_str("--answer-type")?,
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
             },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            "templates" => AppArguments::Templates,
+            "solve" => {
+                let submit = args.opt_value_from_str("--submit")?;
+                let watch = args.contains("--watch");
+                let example = args.contains("--example");
+
+                if example && !watch {
+                    return Err(
+                        "`--example` requires `--watch`, e.g. `cargo solve 5 --watch --example`."
+                            .into(),
+                    );
+                }
+                if watch && submit.is_some() {
+                    return Err("`--submit` can not be combined with `--watch`.".into());
+                }
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    release: args.contains("--release"),
+                    submit,
+                    dhat: args.contains("--dhat"),
+                    threads: args.opt_value_from_str("--threads")?,
+                    part: args.opt_value_from_str("--part")?,
+                    show_output: args.contains("--show-output"),
+                    verbosity: if args.contains("-vv") {
+                        2
+                    } else {
+                        u8::from(args.contains("-v"))
+                    },
+                    explain: args.contains("--explain"),
+                    explain_json: args.opt_value_from_str("--explain-json")?,
+                    profile_spans: args.contains("--profile-spans"),
+                    watch,
+                    example,
+                }
             }
+            "completions" => AppArguments::Completions {
+                shell: args.free_from_str()?,
+            },
+            #[cfg(feature = "today")]
+            "today" => AppArguments::Today,
+            _ => unreachable!("commands are looked up in `help::COMMANDS`"),
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
-
         Ok(app_args)
     }
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+    let args = match parse() {
+        Ok(args) => args,
+        Err(err) => Error::Usage(err.to_string()).exit(),
+    };
+
+    if let Err(err) = run(args) {
+        err.exit();
+    }
+}
+
+fn run(args: AppArguments) -> Result<()> {
+    match args {
+        AppArguments::All {
+            days,
+            release,
+            jobs,
+            threads,
+            part,
+        } => all::handle(
+            &resolve_days(days)?.unwrap_or_else(|| all_days().collect()),
+            release,
+            jobs,
+            threads,
+            part,
+        ),
+        AppArguments::Time {
+            days,
+            all,
+            store,
+            rev,
+            merge_machines,
+            noise_control,
+            threads,
+            part,
+        } => time::handle(
+            resolve_days(days)?,
+            all,
+            store,
+            rev.as_deref(),
+            merge_machines,
+            noise_control,
+            threads,
+            part,
+        ),
+        AppArguments::TimeScaling { day, threads, part } => {
+            time::handle_scaling(day, threads, part)
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+        AppArguments::Download { days, all } => {
+            let days: Vec<Day> = if all {
+                download::unlocked_days()
+            } else {
+                let days = resolve_days(days)?.unwrap_or_default();
+                all_days().filter(|day| days.contains(day)).collect()
+            };
+
+            // a single day is always downloaded, bulk downloads skip days that have an input.
+            match days[..] {
+                [day] if !all => download::handle(day),
+                _ => download::handle_many(&days),
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+        }
+        AppArguments::Dashboard => dashboard::handle(),
+        AppArguments::Status { days } => {
+            status::handle(&resolve_days(days)?.unwrap_or_else(|| all_days().collect()));
+            Ok(())
+        }
+        AppArguments::Read { day, refresh, part } => read::handle(day, refresh, part),
+        AppArguments::Scaffold {
+            download,
+            overwrite,
+            template,
+            answer_type,
+            day,
+        } => {
+            // download first, so that the template can use the puzzle title and example answers.
+            if download {
+                download::handle(day)?;
             }
+            scaffold::handle(day, overwrite, template.as_deref(), answer_type.as_deref())
+        }
+        AppArguments::Templates => {
+            templates::handle();
+            Ok(())
+        }
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            threads,
+            part,
+            show_output,
+            verbosity,
+            explain,
+            explain_json,
+            profile_spans,
+            watch,
+            example,
+        } => solve::handle(
+            day,
+            release,
+            dhat,
+            submit,
+            threads,
+            part,
+            show_output,
+            verbosity,
+            explain,
+            explain_json.as_deref(),
+            profile_spans,
+            watch,
+            example,
+        ),
+        AppArguments::Help { command } => {
+            help::handle(command);
+            Ok(())
+        }
+        AppArguments::Completions { shell } => {
+            completions::handle(shell);
+            Ok(())
+        }
+        #[cfg(feature = "today")]
+        AppArguments::Today => match Day::today() {
+            Some(day) => {
+                download::handle(day)?;
+                scaffold::handle(day, false, None, None)?;
+                read::handle(day, false, None)
+            }
+            None => Err(Error::Usage(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day."
+                    .into(),
+            )),
         },
+    }
+}
+
+/// Resolves a day expression passed on the command line, fails if it does not select any day.
+fn resolve_days(days: Option<DayExpr>) -> Result<Option<HashSet<Day>>> {
+    let Some(days) = days.map(|x| x.resolve()) else {
+        return Ok(None);
     };
+
+    if days.is_empty() {
+        return Err(Error::Usage(
+            "no days match the given day expression.".into(),
+        ));
+    }
+
+    Ok(Some(days))
 }
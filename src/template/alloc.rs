@@ -0,0 +1,70 @@
+/// Global allocator that tracks the peak heap usage of a solution, installed by the `solution!` macro.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+pub struct PeakAlloc;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+fn grow(size: usize) {
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn shrink(size: usize) {
+    CURRENT.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            shrink(layout.size());
+            grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Called by the `solution!` macro before anything is measured.
+pub fn install() {
+    INSTALLED.store(true, Ordering::Relaxed);
+}
+
+/// Run `func` and return the peak number of bytes it allocated on top of what was already allocated.
+/// The peak is `None` if the tracking allocator is not installed, e.g. with the `dhat-heap` feature.
+pub(crate) fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<u64>) {
+    let base = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+
+    let result = func();
+
+    let peak = PEAK.load(Ordering::Relaxed).saturating_sub(base);
+    (
+        result,
+        INSTALLED.load(Ordering::Relaxed).then_some(peak as u64),
+    )
+}
@@ -1,55 +1,42 @@
 /// Wrapper module around the "aoc-cli" command-line.
-use std::{
-    fmt::Display,
-    process::{Command, Output, Stdio},
-};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::{env, fs};
 
+use crate::template::config::{self, Session};
+use crate::template::error::{Error, Result};
 use crate::template::Day;
 
-#[derive(Debug)]
-pub enum AocCommandError {
-    CommandNotFound,
-    CommandNotCallable,
-    BadExitStatus(Output),
-}
-
-impl Display for AocCommandError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-            AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
-            }
-        }
-    }
-}
+const MISSING_AOC_CLI: Error = Error::MissingTool {
+    tool: "aoc",
+    hint: Some("Try running \"cargo install aoc-cli\" to install it."),
+};
 
-pub fn check() -> Result<(), AocCommandError> {
+pub fn check() -> Result<()> {
     Command::new("aoc")
         .arg("-V")
         .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
+        .map_err(|_| MISSING_AOC_CLI)?;
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
-
+/// Downloads the puzzle description only, e.g. to fetch part two after part one was solved.
+pub fn download_puzzle(day: Day) -> Result<Output> {
     let args = build_args(
-        "read",
+        "download",
         &[
-            "--description-only".into(),
+            "--overwrite".into(),
+            "--puzzle-only".into(),
             "--puzzle-file".into(),
-            puzzle_path,
+            get_puzzle_path(day),
         ],
         day,
     );
 
-    call_aoc_cli(&args)
+    call_aoc_cli(&args, Stdio::piped())
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
+pub fn download(day: Day) -> Result<Output> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
 
@@ -65,61 +52,182 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
         day,
     );
 
-    let output = call_aoc_cli(&args)?;
+    let output = call_aoc_cli(&args, Stdio::inherit())?;
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits an answer, returns [`Error::WrongAnswer`] if it was rejected.
+pub fn submit(day: Day, part: u8, result: &str) -> Result<Output> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // NOTE: aoc-cli exits successfully for rejected answers, the verdict has to be read from its output.
+    let output = call_aoc_cli(&args, Stdio::piped())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    print!("{stdout}");
+
+    match rejection(day, part, &stdout) {
+        Some(err) => Err(err),
+        None => Ok(output),
+    }
+}
+
+/// Reads the verdict from the output of `aoc submit`, returns the error if the answer was not accepted.
+fn rejection(day: Day, part: u8, output: &str) -> Option<Error> {
+    // aoc-cli wraps the response to the width of the terminal, so a phrase can span several lines.
+    let output = output.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if output.contains("not the right answer") {
+        return Some(Error::WrongAnswer { day, part });
+    }
+
+    if output.contains("You gave an answer too recently") {
+        return Some(Error::Network(
+            "the answer was submitted too soon after the previous one, wait before trying again."
+                .into(),
+        ));
+    }
+
+    None
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+/// Returns the session token the same way aoc-cli looks it up, for requests that are not sent via aoc-cli.
+pub fn session_token() -> Result<String> {
+    let missing = || {
+        Error::Usage(
+            "no session token found, see \"Configure aoc-cli integration\" in the readme.".into(),
+        )
+    };
+
+    let token = match &config::get().session {
+        Session::Env(var) => env::var(var).map_err(|_| {
+            Error::Usage(format!(
+                "the session token variable `{var}` configured in aoc.toml is not set."
+            ))
+        })?,
+        Session::File(path) => fs::read_to_string(path).map_err(|_| missing())?,
+        Session::Default => env::var("ADVENT_OF_CODE_SESSION")
+            .ok()
+            .or_else(|| {
+                let home = env::var("HOME").ok()?;
+                [".adventofcode.session", ".config/adventofcode.session"]
+                    .iter()
+                    .find_map(|file| fs::read_to_string(Path::new(&home).join(file)).ok())
+            })
+            .ok_or_else(missing)?,
+    };
+
+    Ok(token.trim().to_string())
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+pub fn get_input_path(day: Day) -> String {
+    config::get().paths.input(day)
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+fn get_puzzle_path(day: Day) -> String {
+    config::get().paths.puzzle(day)
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+    let config = config::get();
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = config.year {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
 
+    if let Session::File(path) = &config.session {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(path.clone());
+    }
+
     cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
 
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+fn call_aoc_cli(args: &[String], stdout: Stdio) -> Result<Output> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    let mut cmd = Command::new("aoc");
+
+    if let Session::Env(var) = &config::get().session {
+        let token = env::var(var).map_err(|_| {
+            Error::Usage(format!(
+                "the session token variable `{var}` configured in aoc.toml is not set."
+            ))
+        })?;
+        cmd.env("ADVENT_OF_CODE_SESSION", token);
+    }
+
+    let output = cmd
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
+        .map_err(|_| MISSING_AOC_CLI)?;
 
     if output.status.success() {
         Ok(output)
     } else {
-        Err(AocCommandError::BadExitStatus(output))
+        Err(Error::Network(format!(
+            "aoc-cli exited with a non-zero status ({}).",
+            output.status
+        )))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::rejection;
+    use crate::day;
+    use crate::template::error::Error;
+
+    // responses as printed by `aoc submit`, which wraps them to the width of the terminal.
+    const WRONG: &str =
+        "That's not the right answer; your answer is too high.  If you're stuck, make sure
+you're using the full input data; there are also some general tips on the about
+page, or you can ask for hints on the subreddit.  Please wait one minute before
+trying again. [Return to Day 1]
+";
+
+    const WRAPPED: &str = "That's not the right
+answer.  If you're stuck, make sure you're using the full input data.
+";
+
+    const TOO_RECENT: &str =
+        "You gave an answer too recently; you have to wait after submitting an answer
+before trying again.  You have 42s left to wait. [Return to Day 1]
+";
+
+    const CORRECT: &str =
+        "That's the right answer!  You are one gold star closer to finding the Chief
+Historian. [Continue to Part Two]
+";
+
+    const SOLVED: &str =
+        "You don't seem to be solving the right level.  Did you already complete it?
+[Return to Day 1]
+";
+
+    #[test]
+    fn reads_submit_verdicts() {
+        for output in [WRONG, WRAPPED] {
+            assert!(matches!(
+                rejection(day!(1), 1, output),
+                Some(Error::WrongAnswer { part: 1, .. })
+            ));
+        }
+        assert!(matches!(
+            rejection(day!(1), 1, TOO_RECENT),
+            Some(Error::Network(_))
+        ));
+        assert!(rejection(day!(1), 1, CORRECT).is_none());
+        assert!(rejection(day!(1), 2, SOLVED).is_none());
     }
 }
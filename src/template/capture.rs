@@ -0,0 +1,260 @@
+/// Redirects the output that solutions print while running, so that it does not garble the result lines.
+use std::io::{stderr, stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+/// Number of lines of a block that are shown if output is collapsed.
+const COLLAPSED_LINES: usize = 5;
+
+/// Output written to stdout and stderr while a part was running.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Captured {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl Captured {
+    pub fn is_empty(&self) -> bool {
+        self.stdout.is_empty() && self.stderr.is_empty()
+    }
+
+    /// Formats the captured output as a block that is printed below the result line of a part.
+    pub fn format(&self, collapsed: bool) -> Vec<String> {
+        let mut lines = vec![];
+
+        for (label, output) in [("stdout", &self.stdout), ("stderr", &self.stderr)] {
+            if output.is_empty() {
+                continue;
+            }
+
+            let count = output.lines().count();
+            let shown = if collapsed { COLLAPSED_LINES } else { count };
+
+            lines.push(format!(
+                "  ┌ {ANSI_ITALIC}{label} ({count} line{}){ANSI_RESET}",
+                if count == 1 { "" } else { "s" }
+            ));
+            lines.extend(output.lines().take(shown).map(|l| format!("  │ {l}")));
+            if count > shown {
+                lines.push(format!(
+                    "  │ {ANSI_ITALIC}… {} more, pass `--show-output` to expand{ANSI_RESET}",
+                    count - shown
+                ));
+            }
+            lines.push("  └".into());
+        }
+
+        lines
+    }
+}
+
+/// Runs `func` and returns everything it wrote to stdout and stderr instead of printing it.
+/// If `func` panics, the captured output is printed before the panic continues.
+pub fn capture<T>(func: impl FnOnce() -> T) -> (T, Captured) {
+    match sys::Redirect::to_files() {
+        Ok(redirect) => match panic::catch_unwind(AssertUnwindSafe(func)) {
+            Ok(result) => (result, redirect.finish()),
+            Err(payload) => {
+                // the panic hook already wrote its message to the captured stderr.
+                let captured = redirect.finish();
+                print!("{}", captured.stdout);
+                eprint!("{}", captured.stderr);
+                panic::resume_unwind(payload)
+            }
+        },
+        // output is not captured on unsupported platforms, better garbled than lost.
+        Err(_) => (func(), Captured::default()),
+    }
+}
+
+/// Runs `func` and discards everything it writes to stdout and stderr, e.g. during benchmark iterations.
+pub fn suppress<T>(func: impl FnOnce() -> T) -> T {
+    match sys::Redirect::to_null() {
+        Ok(redirect) => {
+            let result = func();
+            redirect.finish();
+            result
+        }
+        Err(_) => func(),
+    }
+}
+
+fn flush() {
+    let _ = stdout().flush();
+    let _ = stderr().flush();
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::{
+        env,
+        fs::{self, File},
+        io,
+        os::fd::AsRawFd,
+        path::PathBuf,
+        process,
+    };
+
+    use super::{flush, Captured};
+
+    const STDOUT_FD: i32 = 1;
+    const STDERR_FD: i32 = 2;
+
+    extern "C" {
+        fn dup(fd: i32) -> i32;
+        fn dup2(src: i32, dst: i32) -> i32;
+        fn close(fd: i32) -> i32;
+    }
+
+    /// Points a file descriptor to another file until it is restored.
+    struct Swap {
+        fd: i32,
+        saved: i32,
+    }
+
+    impl Swap {
+        fn new(fd: i32, target: &File) -> io::Result<Self> {
+            // SAFETY: `dup` and `dup2` only operate on file descriptors, which are checked for errors.
+            unsafe {
+                let saved = dup(fd);
+                if saved < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                if dup2(target.as_raw_fd(), fd) < 0 {
+                    let error = io::Error::last_os_error();
+                    close(saved);
+                    return Err(error);
+                }
+                Ok(Self { fd, saved })
+            }
+        }
+    }
+
+    impl Drop for Swap {
+        fn drop(&mut self) {
+            // SAFETY: `saved` is a descriptor owned by this value.
+            unsafe {
+                dup2(self.saved, self.fd);
+                close(self.saved);
+            }
+        }
+    }
+
+    pub struct Redirect {
+        paths: Option<(PathBuf, PathBuf)>,
+        swaps: Vec<Swap>,
+    }
+
+    impl Redirect {
+        pub fn to_files() -> io::Result<Self> {
+            let path =
+                |name: &str| env::temp_dir().join(format!("aoc-capture-{}-{name}", process::id()));
+            let (stdout_path, stderr_path) = (path("stdout"), path("stderr"));
+
+            flush();
+            let swaps = vec![
+                Swap::new(STDOUT_FD, &File::create(&stdout_path)?)?,
+                Swap::new(STDERR_FD, &File::create(&stderr_path)?)?,
+            ];
+
+            Ok(Self {
+                paths: Some((stdout_path, stderr_path)),
+                swaps,
+            })
+        }
+
+        pub fn to_null() -> io::Result<Self> {
+            let null = File::options().write(true).open("/dev/null")?;
+
+            flush();
+            let swaps = vec![Swap::new(STDOUT_FD, &null)?, Swap::new(STDERR_FD, &null)?];
+
+            Ok(Self { paths: None, swaps })
+        }
+
+        /// Restores stdout and stderr and returns what was written to them in the meantime.
+        pub fn finish(mut self) -> Captured {
+            flush();
+            self.swaps.clear();
+
+            let Some((stdout_path, stderr_path)) = self.paths.take() else {
+                return Captured::default();
+            };
+
+            let read = |path: PathBuf| {
+                let contents = fs::read_to_string(&path).unwrap_or_default();
+                let _ = fs::remove_file(path);
+                contents
+            };
+
+            Captured {
+                stdout: read(stdout_path),
+                stderr: read(stderr_path),
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod sys {
+    use std::io;
+
+    use super::Captured;
+
+    pub struct Redirect;
+
+    impl Redirect {
+        pub fn to_files() -> io::Result<Self> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+
+        pub fn to_null() -> io::Result<Self> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+
+        pub fn finish(self) -> Captured {
+            Captured::default()
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Captured;
+
+    #[test]
+    fn formats_output_blocks() {
+        let captured = Captured {
+            stdout: "a\nb\n".into(),
+            stderr: "warning\n".into(),
+        };
+
+        let lines = captured.format(false);
+        assert_eq!(lines.len(), 7);
+        assert!(lines[0].contains("stdout (2 lines)"));
+        assert_eq!(lines[1], "  │ a");
+        assert_eq!(lines[2], "  │ b");
+        assert!(lines[4].contains("stderr (1 line)"));
+        assert_eq!(lines[5], "  │ warning");
+    }
+
+    #[test]
+    fn collapses_long_output() {
+        let captured = Captured {
+            stdout: (0..8).map(|i| format!("{i}\n")).collect(),
+            stderr: String::new(),
+        };
+
+        let lines = captured.format(true);
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[5], "  │ 4");
+        assert!(lines[6].contains("… 3 more"));
+    }
+
+    #[test]
+    fn skips_empty_output() {
+        assert!(Captured::default().is_empty());
+        assert!(Captured::default().format(false).is_empty());
+    }
+}
@@ -1,5 +1,27 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::collections::HashSet;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    error::Result,
+    run_multi::{run_multi, run_multi_parallel, RunOptions},
+    Day,
+};
+
+pub fn handle(
+    days: &HashSet<Day>,
+    is_release: bool,
+    jobs: Option<usize>,
+    threads: Option<usize>,
+    part: Option<u8>,
+) -> Result<()> {
+    let options = RunOptions {
+        is_release,
+        is_timed: false,
+        threads,
+        part,
+    };
+
+    match jobs {
+        Some(jobs) => run_multi_parallel(days, &options, jobs),
+        None => run_multi(days, &options).map(|_| ()),
+    }
 }
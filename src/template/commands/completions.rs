@@ -0,0 +1,212 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::commands::help::{CommandSpec, COMMANDS};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = ShellFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(ShellFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Shell`].
+#[derive(Debug)]
+pub struct ShellFromStrError;
+
+impl std::error::Error for ShellFromStrError {}
+
+impl Display for ShellFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `bash`, `zsh` or `fish`")
+    }
+}
+
+/// Prints a script that completes the template commands and their flags after `cargo`.
+/// Completions of other cargo commands are delegated to the shell's existing cargo completion.
+pub fn handle(shell: Shell) {
+    let script = match shell {
+        Shell::Bash => bash(COMMANDS),
+        Shell::Zsh => zsh(COMMANDS),
+        Shell::Fish => fish(COMMANDS),
+    };
+
+    print!("{script}");
+}
+
+fn flag_names(command: &CommandSpec) -> String {
+    command
+        .flags
+        .iter()
+        .map(|f| f.name)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escapes single quotes for use in a single-quoted shell string.
+fn quote(s: &str) -> String {
+    s.replace('\'', "'\\''")
+}
+
+fn bash(commands: &[CommandSpec]) -> String {
+    let names: Vec<&str> = commands.iter().map(|c| c.name).collect();
+    let mut cases = String::new();
+
+    for command in commands {
+        cases.push_str(&format!(
+            "        {}) COMPREPLY=($(compgen -W '{}' -- \"$cur\")); return ;;\n",
+            command.name,
+            flag_names(command)
+        ));
+    }
+
+    format!(
+        r#"# completions for the advent of code template commands
+_aoc_template() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+
+    if [[ $COMP_CWORD -ge 2 ]]; then
+        case "${{COMP_WORDS[1]}}" in
+{cases}        esac
+    fi
+
+    if declare -F _cargo >/dev/null; then
+        _cargo "$@"
+    fi
+
+    if [[ $COMP_CWORD -eq 1 ]]; then
+        COMPREPLY+=($(compgen -W '{}' -- "$cur"))
+    fi
+}}
+
+# load the default cargo completion before wrapping it.
+if ! declare -F _cargo >/dev/null && declare -F _completion_loader >/dev/null; then
+    _completion_loader cargo
+fi
+complete -F _aoc_template cargo
+"#,
+        names.join(" ")
+    )
+}
+
+fn zsh(commands: &[CommandSpec]) -> String {
+    let mut cases = String::new();
+    let mut descriptions = vec![];
+
+    for command in commands {
+        let flags: Vec<String> = command
+            .flags
+            .iter()
+            .map(|f| {
+                let value = if f.value.is_some() { ":value:" } else { "" };
+                format!(
+                    "'{}[{}]{value}'",
+                    f.name,
+                    quote(&f.about.replace(['[', ']'], ""))
+                )
+            })
+            .collect();
+
+        cases.push_str(&format!(
+            "            {}) _arguments -s {}; return ;;\n",
+            command.name,
+            flags.join(" ")
+        ));
+        descriptions.push(format!("'{}:{}'", command.name, quote(command.about)));
+    }
+
+    format!(
+        r#"# completions for the advent of code template commands
+_aoc_template() {{
+    if (( CURRENT > 2 )); then
+        case $words[2] in
+{cases}        esac
+    fi
+
+    if (( CURRENT == 2 )); then
+        local -a commands
+        commands=({})
+        _describe 'advent of code commands' commands
+    fi
+
+    (( $+functions[_cargo] )) || autoload -Uz _cargo
+    _cargo "$@"
+}}
+
+compdef _aoc_template cargo
+"#,
+        descriptions.join(" ")
+    )
+}
+
+fn fish(commands: &[CommandSpec]) -> String {
+    let mut lines = vec!["# completions for the advent of code template commands".to_string()];
+
+    for command in commands {
+        lines.push(format!(
+            "complete -c cargo -n '__fish_use_subcommand' -a '{}' -d '{}'",
+            command.name,
+            quote(command.about)
+        ));
+
+        for flag in command.flags {
+            let name = match flag.name.strip_prefix("--") {
+                Some(long) => format!("-l {long}"),
+                None => format!("-o {}", flag.name.trim_start_matches('-')),
+            };
+            let value = if flag.value.is_some() { " -r" } else { "" };
+
+            lines.push(format!(
+                "complete -c cargo -n '__fish_seen_subcommand_from {}' {name}{value} -d '{}'",
+                command.name,
+                quote(flag.about)
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bash, fish, zsh, Shell};
+    use crate::template::commands::help::COMMANDS;
+
+    #[test]
+    fn parses_shells() {
+        assert_eq!("bash".parse::<Shell>().unwrap(), Shell::Bash);
+        assert_eq!("fish".parse::<Shell>().unwrap(), Shell::Fish);
+        assert!("powershell".parse::<Shell>().is_err());
+    }
+
+    #[test]
+    fn completes_all_commands_and_flags() {
+        let scripts = [bash(COMMANDS), zsh(COMMANDS), fish(COMMANDS)];
+
+        for script in &scripts {
+            for command in COMMANDS {
+                assert!(script.contains(command.name));
+            }
+        }
+
+        assert!(scripts[0].contains("solve) COMPREPLY=($(compgen -W '--release --dhat"));
+        assert!(scripts[1].contains("'--submit[Submit the answer of a part via aoc-cli]:value:'"));
+        assert!(scripts[2]
+            .contains("complete -c cargo -n '__fish_seen_subcommand_from time' -l store -d"));
+        assert!(scripts[2].contains("-n '__fish_seen_subcommand_from solve' -o vv -d"));
+    }
+}
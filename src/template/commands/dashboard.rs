@@ -0,0 +1,517 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+use std::{env, thread};
+
+use crate::template::error::{Context, Result};
+use crate::template::run_multi::child_commands::{self, OnLine};
+use crate::template::run_multi::{get_path_for_bin, RunOptions};
+use crate::template::terminal::{
+    fit, read_key, sanitize, Key, RawTerminal, ANSI_DIM, ANSI_NORMAL_INTENSITY, ANSI_REVERSE,
+};
+use crate::template::timings::{Timing, Timings, TimingsStore};
+use crate::template::{all_days, config, Day, ANSI_BOLD, ANSI_RESET};
+
+const COLUMNS: usize = 5;
+const CELL_WIDTH: usize = 15;
+/// Rows used by everything but the output pane: title, grid, details, pane heading and key help.
+const FIXED_ROWS: usize = 2 + 5 * 3 + 3 + 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    Solve,
+    Test,
+    Time,
+    Download,
+    Read,
+}
+
+impl Action {
+    fn from_key(c: char) -> Option<Self> {
+        match c {
+            's' => Some(Action::Solve),
+            't' => Some(Action::Test),
+            'b' => Some(Action::Time),
+            'd' => Some(Action::Download),
+            'r' => Some(Action::Read),
+            _ => None,
+        }
+    }
+
+    fn command_line(self, day: Day) -> String {
+        match self {
+            Action::Solve => format!("cargo solve {day}"),
+            Action::Test => format!("cargo test --bin {day}"),
+            Action::Time => format!("cargo time {day}"),
+            Action::Download => format!("cargo download {day}"),
+            Action::Read => format!("cargo read {day}"),
+        }
+    }
+
+    fn needs_solution(self) -> bool {
+        matches!(self, Action::Solve | Action::Test | Action::Time)
+    }
+}
+
+/// Answer of a part in the latest run of the session.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+enum Answer {
+    #[default]
+    NotRun,
+    Missing,
+    Value(String),
+}
+
+impl Answer {
+    fn mark(&self) -> char {
+        match self {
+            Answer::NotRun => '·',
+            Answer::Missing => '✖',
+            Answer::Value(_) => '✓',
+        }
+    }
+}
+
+enum Event {
+    Key(Key),
+    Line(String),
+    Done {
+        success: bool,
+        answers: Option<[Answer; 2]>,
+    },
+}
+
+/// Files and stored benchmarks of a day.
+struct DayStatus {
+    solution: bool,
+    input: bool,
+    example: bool,
+    timing: Option<Timing>,
+}
+
+fn read_statuses() -> Vec<DayStatus> {
+    let store = TimingsStore::read_from_file();
+    let timings = store
+        .sets
+        .iter()
+        .fold(Timings::default(), |acc, t| acc.merge_mixed(t));
+    let paths = &config::get().paths;
+
+    all_days()
+        .map(|day| DayStatus {
+            solution: Path::new(&get_path_for_bin(day)).exists(),
+            input: Path::new(&paths.input(day)).exists(),
+            example: Path::new(&paths.example(day))
+                .metadata()
+                .is_ok_and(|x| x.len() > 0),
+            timing: timings.data.iter().find(|t| t.day == day).cloned(),
+        })
+        .collect()
+}
+
+struct Dashboard {
+    selected: usize,
+    statuses: Vec<DayStatus>,
+    answers: HashMap<Day, [Answer; 2]>,
+    /// The day and action of the latest job, and whether it is still running.
+    job: Option<(Day, Action, bool)>,
+    output: Vec<String>,
+    /// Number of lines the output pane is scrolled up from the bottom.
+    scroll: usize,
+}
+
+/// Shows all days in a grid and runs commands for the selected day, streaming their output into a pane.
+pub fn handle() -> Result<()> {
+    let terminal = RawTerminal::enter()?;
+    let (tx, rx) = mpsc::channel();
+
+    let keys = tx.clone();
+    thread::spawn(move || {
+        let mut stdin = io::stdin().lock();
+        while let Some(key) = read_key(&mut stdin) {
+            if keys.send(Event::Key(key)).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut dashboard = Dashboard {
+        selected: 0,
+        statuses: read_statuses(),
+        answers: HashMap::new(),
+        job: None,
+        output: vec![],
+        scroll: 0,
+    };
+
+    loop {
+        dashboard.draw();
+
+        let Ok(event) = rx.recv() else {
+            break;
+        };
+
+        // apply everything that queued up while drawing before drawing again.
+        for event in std::iter::once(event).chain(rx.try_iter()) {
+            if !dashboard.update(event, &tx) {
+                drop(terminal);
+                return Ok(());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl Dashboard {
+    fn day(&self) -> Day {
+        all_days().nth(self.selected).unwrap()
+    }
+
+    fn is_running(&self) -> bool {
+        self.job.is_some_and(|(_, _, running)| running)
+    }
+
+    /// Applies an event, returns `false` to quit.
+    fn update(&mut self, event: Event, tx: &mpsc::Sender<Event>) -> bool {
+        match event {
+            Event::Key(key) => return self.on_key(key, tx),
+            Event::Line(line) => {
+                self.output.push(sanitize(&line));
+                if self.scroll > 0 {
+                    self.scroll += 1;
+                }
+            }
+            Event::Done { success, answers } => {
+                if let Some((day, action, running)) = &mut self.job {
+                    *running = false;
+                    if let Some(answers) = answers {
+                        self.answers.insert(*day, answers);
+                    }
+                    let verdict = if success { "✓ done" } else { "✖ failed" };
+                    self.output
+                        .push(format!("{verdict}: {}", action.command_line(*day)));
+                }
+                self.statuses = read_statuses();
+            }
+        }
+        true
+    }
+
+    fn on_key(&mut self, key: Key, tx: &mpsc::Sender<Event>) -> bool {
+        let days = self.statuses.len();
+
+        match key {
+            Key::Char('q') | Key::CtrlC => return false,
+            Key::Left | Key::Char('h') => self.selected = self.selected.saturating_sub(1),
+            Key::Right | Key::Char('l') => self.selected = (self.selected + 1).min(days - 1),
+            Key::Up | Key::Char('k') => self.selected = self.selected.saturating_sub(COLUMNS),
+            Key::Down | Key::Char('j') => self.selected = (self.selected + COLUMNS).min(days - 1),
+            Key::PageUp => {
+                self.scroll = (self.scroll + self.pane_height()).min(self.output.len());
+            }
+            Key::PageDown => self.scroll = self.scroll.saturating_sub(self.pane_height()),
+            Key::Enter => self.start(Action::Solve, tx),
+            Key::Char(c) => {
+                if let Some(action) = Action::from_key(c) {
+                    self.start(action, tx);
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn start(&mut self, action: Action, tx: &mpsc::Sender<Event>) {
+        if self.is_running() {
+            return;
+        }
+
+        let day = self.day();
+        self.output.clear();
+        self.scroll = 0;
+
+        if action.needs_solution() && !self.statuses[self.selected].solution {
+            self.output.push(format!(
+                "Day {day} has no solution yet, run `cargo scaffold {day}` first."
+            ));
+            return;
+        }
+
+        self.job = Some((day, action, true));
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let line_tx = tx.clone();
+            let on_line: OnLine = Arc::new(move |line| {
+                line_tx.send(Event::Line(line)).ok();
+            });
+            let (success, answers) = run(day, action, &on_line);
+            tx.send(Event::Done { success, answers }).ok();
+        });
+    }
+
+    fn pane_height(&self) -> usize {
+        RawTerminal::size().0.saturating_sub(FIXED_ROWS).max(3)
+    }
+
+    fn draw(&self) {
+        let (rows, cols) = RawTerminal::size();
+        let mut lines = vec![];
+
+        let year = config::get()
+            .year
+            .map(|x| format!(" {x}"))
+            .unwrap_or_default();
+        lines.push(format!("{ANSI_BOLD}🎄 Advent of Code{year}{ANSI_RESET}"));
+        lines.push(String::new());
+
+        let days: Vec<Day> = all_days().collect();
+        for (row, days) in days.chunks(COLUMNS).enumerate() {
+            let cells: Vec<[String; 3]> = days
+                .iter()
+                .enumerate()
+                .map(|(column, &day)| self.cell(day, row * COLUMNS + column == self.selected))
+                .collect();
+            for line in 0..3 {
+                lines.push(cells.iter().map(|cell| cell[line].as_str()).collect());
+            }
+        }
+
+        lines.push(String::new());
+        lines.push(self.details());
+        lines.push(String::new());
+
+        let heading = match self.job {
+            Some((day, action, running)) => format!(
+                "── {}{} ",
+                action.command_line(day),
+                if running { " (running)" } else { "" }
+            ),
+            None => "── output ".into(),
+        };
+        lines.push(format!(
+            "{ANSI_DIM}{heading}{}{ANSI_RESET}",
+            "─".repeat(cols.saturating_sub(heading.chars().count()))
+        ));
+
+        let height = rows.saturating_sub(FIXED_ROWS).max(3);
+        let end = self.output.len().saturating_sub(self.scroll);
+        let start = end.saturating_sub(height);
+        for i in 0..height {
+            let line = self.output.get(start + i).filter(|_| start + i < end);
+            lines.push(
+                line.map(|x| fit(x, cols).trim_end().to_string())
+                    .unwrap_or_default(),
+            );
+        }
+
+        lines.push(fit(
+            "←↑↓→ select  s solve  t test  b time  d download  r read  PgUp/PgDn scroll  q quit",
+            cols,
+        ));
+
+        RawTerminal::draw(&lines);
+    }
+
+    /// Renders the three lines of a day's cell: its number, which files exist and the latest answers.
+    fn cell(&self, day: Day, selected: bool) -> [String; 3] {
+        let status = &self.statuses[usize::from(day.into_inner()) - 1];
+        let answers = self.answers.get(&day).cloned().unwrap_or_default();
+        let width = CELL_WIDTH - 1;
+
+        let file = |name: &str, exists: bool| {
+            if exists {
+                name.to_string()
+            } else {
+                format!("{ANSI_DIM}{name}{ANSI_NORMAL_INTENSITY}")
+            }
+        };
+        let timing = status.timing.as_ref().map_or(String::new(), |t| {
+            format!("{:.1?}", Duration::from_nanos(t.total_nanos as u64))
+        });
+
+        let lines = [
+            fit(&format!(" Day {day}"), width),
+            format!(
+                " {} {} {}    ",
+                file("src", status.solution),
+                file("in", status.input),
+                file("ex", status.example)
+            ),
+            fit(
+                &format!(" {}{} {timing}", answers[0].mark(), answers[1].mark()),
+                width,
+            ),
+        ];
+
+        lines.map(|line| {
+            if selected {
+                format!("{ANSI_REVERSE}{line}{ANSI_RESET} ")
+            } else {
+                format!("{line} ")
+            }
+        })
+    }
+
+    fn details(&self) -> String {
+        let day = self.day();
+        let status = &self.statuses[self.selected];
+        let answers = self.answers.get(&day).cloned().unwrap_or_default();
+
+        let part = |n: usize| {
+            let answer = match &answers[n] {
+                Answer::NotRun => "-".to_string(),
+                Answer::Missing => "✖".to_string(),
+                Answer::Value(x) => format!("{ANSI_BOLD}{x}{ANSI_RESET}"),
+            };
+            let timing = status.timing.as_ref().and_then(|t| {
+                if n == 0 {
+                    t.part_1.clone()
+                } else {
+                    t.part_2.clone()
+                }
+            });
+            match timing {
+                Some(timing) => format!("Part {}: {answer} ({timing})", n + 1),
+                None => format!("Part {}: {answer}", n + 1),
+            }
+        };
+
+        format!(
+            "{ANSI_BOLD}Day {day}{ANSI_RESET}   {}   {}",
+            part(0),
+            part(1)
+        )
+    }
+}
+
+/// Runs an action, returns whether it succeeded and, for `solve`, the answers.
+fn run(day: Day, action: Action, on_line: &OnLine) -> (bool, Option<[Answer; 2]>) {
+    if action == Action::Solve {
+        return match child_commands::run_solution_streamed(
+            day,
+            &RunOptions::default(),
+            on_line.clone(),
+        ) {
+            Ok(output) => (output.success, Some(parse_answers(&output.stdout))),
+            Err(err) => {
+                on_line(err.to_string());
+                (false, None)
+            }
+        };
+    }
+
+    let command = match action {
+        Action::Test => {
+            let mut cmd = Command::new("cargo");
+            cmd.args(["test", "--bin", &day.to_string()])
+                .env("RUST_BACKTRACE", "0");
+            Ok(cmd)
+        }
+        // the other actions run the commands of this binary.
+        _ => env::current_exe()
+            .context("Failed to locate the template binary")
+            .map(|exe| {
+                let name = match action {
+                    Action::Time => "time",
+                    Action::Download => "download",
+                    _ => "read",
+                };
+                let mut cmd = Command::new(exe);
+                cmd.args([name, &day.to_string()]);
+                cmd
+            }),
+    };
+
+    match command.and_then(|cmd| stream(cmd, on_line)) {
+        Ok(success) => (success, None),
+        Err(err) => {
+            on_line(err.to_string());
+            (false, None)
+        }
+    }
+}
+
+/// Runs `cmd`, passing its stdout and stderr lines to `on_line`. Returns whether it succeeded.
+fn stream(mut cmd: Command, on_line: &OnLine) -> Result<bool> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context(format!(
+            "Failed to run {}",
+            cmd.get_program().to_string_lossy()
+        ))?;
+
+    let stderr = child.stderr.take().map(BufReader::new);
+    let stderr_thread = thread::spawn({
+        let on_line = on_line.clone();
+        move || {
+            for line in stderr
+                .into_iter()
+                .flat_map(|x| x.lines().map_while(Result::ok))
+            {
+                on_line(line);
+            }
+        }
+    });
+
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            on_line(line);
+        }
+    }
+
+    stderr_thread.join().ok();
+    let status = child.wait().context("Failed to wait for the command")?;
+    Ok(status.success())
+}
+
+/// Reads the answers from the result lines of a solution, e.g. `Part 1: 42 (1.2ms)`.
+fn parse_answers(stdout: &[String]) -> [Answer; 2] {
+    let mut answers = [Answer::NotRun, Answer::NotRun];
+
+    for line in stdout {
+        let line = sanitize(line);
+        let Some((part, rest)) = line.strip_prefix("Part ").and_then(|x| x.split_once(": ")) else {
+            continue;
+        };
+        let index = match part {
+            "1" => 0,
+            "2" => 1,
+            _ => continue,
+        };
+
+        let answer = rest.split(" (").next().unwrap_or_default().trim();
+        answers[index] = match answer {
+            "✖" | "" => Answer::Missing,
+            answer => Answer::Value(answer.to_string()),
+        };
+    }
+
+    answers
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_answers, Answer};
+
+    #[test]
+    fn parses_answers() {
+        let stdout = vec![
+            "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)".to_string(),
+            "  naive        17.5ms  14.58x slower".to_string(),
+            "Part 2: ✖             ".to_string(),
+        ];
+
+        assert_eq!(
+            parse_answers(&stdout),
+            [Answer::Value("42".into()), Answer::Missing]
+        );
+        assert_eq!(parse_answers(&[]), [Answer::NotRun, Answer::NotRun]);
+    }
+}
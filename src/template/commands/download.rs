@@ -1,14 +1,307 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use std::cmp::Ordering;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs, thread};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+use crate::template::error::{Context, Error, Result};
+use crate::template::{all_days, aoc_cli, config, markdown, Day, ANSI_BOLD, ANSI_RESET};
+
+/// Pause between two downloads of a bulk download, each download requests the puzzle and the input.
+const THROTTLE: Duration = Duration::from_secs(5);
+
+/// Puzzles unlock at midnight EST.
+const SERVER_UTC_OFFSET_SECS: i64 = -5 * 3600;
+
+/// Identifies the template in the User-Agent of bulk downloads, followed by the `contact` of `aoc.toml`.
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+pub fn handle(day: Day) -> Result<()> {
+    aoc_cli::check()?;
+    aoc_cli::download(day)?;
+    Ok(())
+}
+
+/// Downloads the inputs and puzzles of `days`, pausing between requests. Days with a valid input are skipped,
+/// so an interrupted download resumes where it stopped when run again.
+///
+/// Puzzles and inputs are downloaded with curl, which sends the User-Agent that the site asks automated tools for.
+pub fn handle_many(days: &[Day]) -> Result<()> {
+    let token = aoc_cli::session_token()?;
+
+    let (skipped, pending): (Vec<Day>, Vec<Day>) =
+        days.iter().partition(|&&day| has_valid_input(day));
+    let mut downloaded = vec![];
+    let mut failed = vec![];
+
+    for (i, &day) in pending.iter().enumerate() {
+        if i > 0 {
+            thread::sleep(THROTTLE);
+        }
+
+        println!(
+            "{ANSI_BOLD}[{}/{}] Day {day}{ANSI_RESET}",
+            i + 1,
+            pending.len()
+        );
+
+        match download_puzzle(day, &token).and_then(|()| download_input(day, &token)) {
+            Ok(_) if has_valid_input(day) => downloaded.push(day),
+            Ok(_) => {
+                eprintln!("The downloaded input of day {day} is empty or incomplete.");
+                failed.push(day);
+            }
+            // the other days are still downloaded, failed ones are retried when the command is run again.
+            Err(Error::Network(message)) => {
+                eprintln!("Error: {message}");
+                failed.push(day);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    let list = |days: &[Day]| {
+        let days: Vec<String> = days.iter().map(ToString::to_string).collect();
+        if days.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", days.join(", "))
+        }
+    };
+
+    println!("---");
+    println!(
+        "🎄 Downloaded {} day(s){}.",
+        downloaded.len(),
+        list(&downloaded)
+    );
+    println!(
+        "🎄 Skipped {} day(s) with an existing input{}.",
+        skipped.len(),
+        list(&skipped)
+    );
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        println!(
+            "🎄 Failed to download {} day(s){}.",
+            failed.len(),
+            list(&failed)
+        );
+        let failed: Vec<String> = failed.iter().map(ToString::to_string).collect();
+        Err(Error::Network(format!(
+            "failed to download day(s) {}, run the command again to retry.",
+            failed.join(", ")
+        )))
+    }
+}
+
+/// Downloads the puzzle description of `day` and writes it as Markdown to its puzzle path, like aoc-cli does.
+fn download_puzzle(day: Day, token: &str) -> Result<()> {
+    let path = config::get().paths.puzzle(day);
+    let page = fetch(&day_url(day), token)?;
+    let puzzle = markdown::from_html(&String::from_utf8_lossy(&page)).ok_or_else(|| {
+        Error::Network(format!("the page of day {day} has no puzzle description."))
+    })?;
+
+    write_download(&path, puzzle.as_bytes())?;
+    println!("🎄 Successfully wrote puzzle to \"{path}\".");
+    Ok(())
+}
+
+/// Downloads the input of `day` to its input path. The file is only replaced once the download completed.
+fn download_input(day: Day, token: &str) -> Result<()> {
+    let path = aoc_cli::get_input_path(day);
+    let input = fetch(&format!("{}/input", day_url(day)), token)?;
+
+    write_download(&path, &input)?;
+    println!("🎄 Successfully wrote input to \"{path}\".");
+    Ok(())
+}
+
+fn day_url(day: Day) -> String {
+    format!(
+        "{}/{}/day/{}",
+        site_url(),
+        event_year(config::get().year, today()),
+        day.into_inner()
+    )
+}
+
+/// Requests `url` with curl, sending the session cookie and the template's User-Agent.
+/// curl prints why a request failed.
+fn fetch(url: &str, token: &str) -> Result<Vec<u8>> {
+    let user_agent = match &config::get().contact {
+        Some(contact) => format!("{USER_AGENT} by {contact}"),
+        None => USER_AGENT.into(),
+    };
+
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--config", "-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|_| Error::MissingTool {
+            tool: "curl",
+            hint: Some("Bulk downloads fetch puzzles and inputs with curl."),
+        })?;
+
+    // the session token is passed on stdin, so that it does not show up in the process list.
+    if let Some(mut stdin) = child.stdin.take() {
+        write!(
+            stdin,
+            "user-agent = \"{}\"\nheader = \"Cookie: session={}\"\n",
+            quote(&user_agent),
+            quote(token)
+        )
+        .context("Failed to pass the request options to curl")?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .context("Failed to wait for curl")?;
+
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(Error::Network(format!("failed to download {url}.")))
+    }
+}
+
+/// Writes a downloaded file. It is written next to its path first, so that an interrupted write leaves no
+/// partial file behind.
+fn write_download(path: &str, contents: &[u8]) -> Result<()> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent).context(format!("Failed to create \"{}\"", parent.display()))?;
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let partial = format!("{path}.part");
+    fs::write(&partial, contents).context(format!("Failed to write \"{partial}\""))?;
+    fs::rename(&partial, path).context(format!("Failed to write \"{path}\""))
+}
+
+/// Base url of the site. `AOC_URL` overrides it, e.g. to download from a local stand-in in tests.
+fn site_url() -> String {
+    env::var("AOC_URL").unwrap_or_else(|_| "https://adventofcode.com".into())
+}
+
+/// Escapes a value of a curl config file.
+fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Returns the days whose puzzles are unlocked in the configured year, or in the latest event if none is set.
+pub fn unlocked_days() -> Vec<Day> {
+    unlocked(config::get().year, today())
+}
+
+/// Returns the current date on the Advent of Code servers.
+fn today() -> (i64, u32, u32) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| i64::try_from(x.as_secs()).unwrap_or(i64::MAX));
+
+    server_date(now)
+}
+
+/// Returns the configured year, or the latest event that started by `date`.
+fn event_year(year: Option<u16>, (today_year, month, _): (i64, u32, u32)) -> i64 {
+    year.map_or(
+        if month == 12 {
+            today_year
+        } else {
+            today_year - 1
+        },
+        i64::from,
+    )
+}
+
+fn unlocked(year: Option<u16>, date: (i64, u32, u32)) -> Vec<Day> {
+    let (today_year, month, day) = date;
+    let year = event_year(year, date);
+
+    // starting with 2025, events have 12 puzzles.
+    let days = if year >= 2025 { 12 } else { 25 };
+
+    let unlocked = match year.cmp(&today_year) {
+        Ordering::Less => days,
+        Ordering::Equal if month == 12 => day.min(days),
+        _ => 0,
     };
+
+    all_days().take(unlocked as usize).collect()
+}
+
+/// Converts seconds since the unix epoch to the date on the Advent of Code servers.
+fn server_date(unix_secs: i64) -> (i64, u32, u32) {
+    let days = (unix_secs + SERVER_UTC_OFFSET_SECS).div_euclid(86_400);
+
+    // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month as u32, day as u32)
+}
+
+fn has_valid_input(day: Day) -> bool {
+    fs::read_to_string(config::get().paths.input(day)).is_ok_and(|input| is_valid_input(&input))
+}
+
+/// Whether a downloaded input is complete. Inputs end with a newline, a missing one hints at an interrupted
+/// download. Error pages served instead of an input are rejected too.
+fn is_valid_input(input: &str) -> bool {
+    const ERRORS: &[&str] = &[
+        "Puzzle inputs differ by user",
+        "Please don't repeatedly request this endpoint",
+        "404 Not Found",
+    ];
+
+    !input.trim().is_empty() && input.ends_with('\n') && !ERRORS.iter().any(|x| input.contains(x))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_valid_input, quote, server_date, unlocked};
+
+    #[test]
+    fn converts_server_dates() {
+        assert_eq!(server_date(0), (1969, 12, 31));
+        // 2024-12-01 05:00 UTC is midnight on the servers.
+        assert_eq!(server_date(1_733_029_200), (2024, 12, 1));
+        assert_eq!(server_date(1_733_029_199), (2024, 11, 30));
+    }
+
+    #[test]
+    fn finds_unlocked_days() {
+        assert_eq!(unlocked(Some(2023), (2024, 6, 1)).len(), 25);
+        assert_eq!(unlocked(Some(2024), (2024, 12, 7)).len(), 7);
+        assert_eq!(unlocked(Some(2024), (2024, 11, 30)).len(), 0);
+        assert_eq!(unlocked(Some(2025), (2026, 10, 19)).len(), 12);
+        assert_eq!(unlocked(None, (2024, 12, 30)).len(), 25);
+        assert_eq!(unlocked(None, (2026, 10, 19)).len(), 12);
+    }
+
+    #[test]
+    fn quotes_curl_config_values() {
+        assert_eq!(quote("a \"b\" \\c"), "a \\\"b\\\" \\\\c");
+    }
+
+    #[test]
+    fn validates_inputs() {
+        assert!(is_valid_input("1 2\n3 4\n"));
+        assert!(!is_valid_input(""));
+        assert!(!is_valid_input("1 2\n3"));
+        assert!(!is_valid_input(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        ));
+    }
 }
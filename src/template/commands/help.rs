@@ -0,0 +1,364 @@
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// A flag accepted by a command.
+pub struct FlagSpec {
+    pub name: &'static str,
+    /// Placeholder of the flag's value, e.g. `<n>`, if it takes one.
+    pub value: Option<&'static str>,
+    pub about: &'static str,
+}
+
+/// Describes a command of the CLI for help texts and shell completions.
+pub struct CommandSpec {
+    pub name: &'static str,
+    /// Positional arguments, e.g. `<day>`.
+    pub args: &'static str,
+    pub about: &'static str,
+    pub flags: &'static [FlagSpec],
+    pub examples: &'static [&'static str],
+}
+
+const fn flag(name: &'static str, about: &'static str) -> FlagSpec {
+    FlagSpec {
+        name,
+        value: None,
+        about,
+    }
+}
+
+const fn option(name: &'static str, value: &'static str, about: &'static str) -> FlagSpec {
+    FlagSpec {
+        name,
+        value: Some(value),
+        about,
+    }
+}
+
+const HELP: FlagSpec = flag("--help", "Print help for this command");
+
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "scaffold",
+        args: "<day>",
+        about: "Create the solution file and empty example input for a day",
+        flags: &[
+            flag(
+                "--download",
+                "Download the puzzle input and description first",
+            ),
+            flag("--overwrite", "Replace an existing solution file"),
+            option(
+                "--template",
+                "<name>",
+                "Built-in or user template to create the solution from",
+            ),
+            option(
+                "--answer-type",
+                "<type>",
+                "Return type of the parts, e.g. u64",
+            ),
+            HELP,
+        ],
+        examples: &[
+            "cargo scaffold 1",
+            "cargo scaffold 1 --download",
+            "cargo scaffold 5 --template grid --answer-type u64",
+        ],
+    },
+    CommandSpec {
+        name: "templates",
+        args: "",
+        about: "List the templates available to `cargo scaffold --template`",
+        flags: &[HELP],
+        examples: &["cargo templates"],
+    },
+    CommandSpec {
+        name: "download",
+        args: "[<days>]",
+        about: "Download puzzle inputs and descriptions via aoc-cli",
+        flags: &[
+            flag("--all", "Download all unlocked days of the event"),
+            HELP,
+        ],
+        examples: &[
+            "cargo download 1",
+            "cargo download 1-5",
+            "cargo download --all",
+        ],
+    },
+    CommandSpec {
+        name: "read",
+        args: "<day>",
+        about: "Print the downloaded puzzle description of a day",
+        flags: &[
+            flag(
+                "--refresh",
+                "Download the description again via aoc-cli, e.g. to unlock part two",
+            ),
+            option("--part", "<n>", "Only print part 1 or 2"),
+            HELP,
+        ],
+        examples: &["cargo read 1", "cargo read 1 --part 2 --refresh"],
+    },
+    CommandSpec {
+        name: "solve",
+        args: "<day>",
+        about: "Run the solution of a day against its puzzle input",
+        flags: &[
+            flag("--release", "Run an optimized build"),
+            flag("--dhat", "Profile heap allocations with DHAT"),
+            option(
+                "--submit",
+                "<part>",
+                "Submit the answer of a part via aoc-cli",
+            ),
+            option("--part", "<n>", "Only run part 1 or 2"),
+            option("--threads", "<n>", "Size of the rayon thread pool"),
+            flag(
+                "--show-output",
+                "Do not collapse output printed by the solution",
+            ),
+            flag("-v", "Print `debug!` messages, implies --show-output"),
+            flag("-vv", "Print `debug!` and `trace!` messages"),
+            flag(
+                "--explain",
+                "Print events recorded with `explain!` as tables",
+            ),
+            option(
+                "--explain-json",
+                "<path>",
+                "Write events recorded with `explain!` to a JSON file",
+            ),
+            flag(
+                "--profile-spans",
+                "Print the tree of spans opened with `span!`",
+            ),
+            flag(
+                "--watch",
+                "Rebuild and rerun whenever the day's sources or data change",
+            ),
+            flag(
+                "--example",
+                "With `--watch`, rerun the example tests instead of the solution",
+            ),
+            HELP,
+        ],
+        examples: &[
+            "cargo solve 1",
+            "cargo solve 1 --release --submit 2",
+            "cargo solve 1 --part 2 -v",
+            "cargo solve 1 --watch --example",
+        ],
+    },
+    CommandSpec {
+        name: "all",
+        args: "[<days>]",
+        about: "Run the solutions of all or the selected days",
+        flags: &[
+            flag("--release", "Run optimized builds"),
+            option("--jobs", "<n>", "Run up to n days concurrently"),
+            option("--part", "<n>", "Only run part 1 or 2"),
+            option("--threads", "<n>", "Size of the rayon thread pool"),
+            HELP,
+        ],
+        examples: &["cargo all", "cargo all 1-5 --release", "cargo all --jobs 8"],
+    },
+    CommandSpec {
+        name: "time",
+        args: "[<days>]",
+        about: "Benchmark changed, all or the selected solutions",
+        flags: &[
+            flag("--all", "Bench all days, not only those that changed"),
+            flag("--store", "Store the timings and update the readme table"),
+            option(
+                "--rev",
+                "<git-ref>",
+                "Compare against the solutions of a git revision",
+            ),
+            flag(
+                "--merge-machines",
+                "Store timings of different machines in one table",
+            ),
+            flag(
+                "--stable",
+                "Pin benchmarks to a core and interleave them in rounds",
+            ),
+            option(
+                "--core",
+                "<n>",
+                "Core to pin benchmarks to, implies --stable",
+            ),
+            option(
+                "--rounds",
+                "<n>",
+                "Number of interleaved rounds, implies --stable",
+            ),
+            option("--part", "<n>", "Only bench part 1 or 2"),
+            option("--threads", "<n>", "Size of the rayon thread pool"),
+            flag("--scaling", "Bench a day with increasing thread counts"),
+            HELP,
+        ],
+        examples: &[
+            "cargo time",
+            "cargo time --all --store",
+            "cargo time 7 --rev HEAD~1",
+            "cargo time 11 --scaling",
+        ],
+    },
+    CommandSpec {
+        name: "status",
+        args: "[<days>]",
+        about: "Print which days have a solution, an input and stored benchmarks",
+        flags: &[HELP],
+        examples: &["cargo status", "cargo status unsolved"],
+    },
+    CommandSpec {
+        name: "dashboard",
+        args: "",
+        about: "Open an interactive overview of all days",
+        flags: &[HELP],
+        examples: &["cargo dashboard"],
+    },
+    #[cfg(feature = "today")]
+    CommandSpec {
+        name: "today",
+        args: "",
+        about: "Scaffold, download and read the current day",
+        flags: &[HELP],
+        examples: &["cargo today"],
+    },
+    CommandSpec {
+        name: "completions",
+        args: "<shell>",
+        about: "Print a completion script for bash, zsh or fish",
+        flags: &[HELP],
+        examples: &[
+            "cargo completions bash >> ~/.bashrc",
+            "cargo completions fish > ~/.config/fish/completions/aoc.fish",
+        ],
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+/// Returns the usage line of a command.
+pub fn usage(command: &CommandSpec) -> String {
+    let mut usage = format!("cargo {}", command.name);
+    if !command.args.is_empty() {
+        usage.push(' ');
+        usage.push_str(command.args);
+    }
+    if command.flags.len() > 1 {
+        usage.push_str(" [flags]");
+    }
+    usage
+}
+
+/// Prints the help of `command`, or an overview of all commands.
+pub fn handle(command: Option<&CommandSpec>) {
+    let text = match command {
+        Some(command) => format_command(command),
+        None => format_overview(),
+    };
+
+    println!("{text}");
+}
+
+fn format_overview() -> String {
+    let mut lines = vec![
+        "Commands to scaffold, run and benchmark Advent of Code solutions.".to_string(),
+        String::new(),
+        format!("{ANSI_BOLD}Usage:{ANSI_RESET} cargo <command> [<args>] [flags]"),
+        String::new(),
+        format!("{ANSI_BOLD}Commands:{ANSI_RESET}"),
+    ];
+
+    let width = COMMANDS.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for command in COMMANDS {
+        lines.push(format!("  {:<width$}  {}", command.name, command.about));
+    }
+
+    lines.push(String::new());
+    lines.push(
+        "Days can be passed as expressions like `1-5`, `3,7,12`, `..10`, `unsolved` or `slow`."
+            .into(),
+    );
+    lines.push("Run `cargo <command> --help` for the flags of a command.".into());
+
+    lines.join("\n")
+}
+
+fn format_command(command: &CommandSpec) -> String {
+    let mut lines = vec![
+        format!("{}.", command.about),
+        String::new(),
+        format!("{ANSI_BOLD}Usage:{ANSI_RESET} {}", usage(command)),
+        String::new(),
+        format!("{ANSI_BOLD}Flags:{ANSI_RESET}"),
+    ];
+
+    let label = |flag: &FlagSpec| match flag.value {
+        Some(value) => format!("{} {value}", flag.name),
+        None => flag.name.to_string(),
+    };
+
+    let width = command
+        .flags
+        .iter()
+        .map(|f| label(f).len())
+        .max()
+        .unwrap_or(0);
+
+    for flag in command.flags {
+        lines.push(format!("  {:<width$}  {}", label(flag), flag.about));
+    }
+
+    if !command.examples.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("{ANSI_BOLD}Examples:{ANSI_RESET}"));
+        for example in command.examples {
+            lines.push(format!("  {ANSI_ITALIC}{example}{ANSI_RESET}"));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find, format_command, format_overview, usage, COMMANDS};
+
+    #[test]
+    fn lists_all_commands() {
+        let overview = format_overview();
+        for command in COMMANDS {
+            assert!(overview.contains(command.name));
+            assert!(overview.contains(command.about));
+        }
+    }
+
+    #[test]
+    fn documents_flags() {
+        let help = format_command(find("solve").unwrap());
+        assert!(help.contains("--dhat"));
+        assert!(help.contains("--submit <part>"));
+        assert!(help.contains("cargo solve 1 --release --submit 2"));
+
+        for (command, flag) in [
+            ("time", "--store"),
+            ("time", "--all"),
+            ("scaffold", "--overwrite"),
+            ("scaffold", "--download"),
+        ] {
+            assert!(format_command(find(command).unwrap()).contains(flag));
+        }
+    }
+
+    #[test]
+    fn formats_usage() {
+        assert_eq!(usage(find("time").unwrap()), "cargo time [<days>] [flags]");
+        assert_eq!(usage(find("status").unwrap()), "cargo status [<days>]");
+    }
+}
@@ -1,15 +1,67 @@
-use std::process;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::error::{Context, Error, Result};
+use crate::template::markdown::{part_section, render};
+use crate::template::terminal::RawTerminal;
+use crate::template::{aoc_cli, config, Day};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Paragraphs are wrapped at the terminal width, but not wider than this.
+const MAX_WIDTH: usize = 100;
+
+/// Prints the puzzle description of a day. The description downloaded to `data/puzzles` is rendered locally,
+/// it is only fetched via aoc-cli if it is missing or `refresh` is set.
+pub fn handle(day: Day, refresh: bool, part: Option<u8>) -> Result<()> {
+    if part.is_some_and(|part| !(1..=2).contains(&part)) {
+        return Err(Error::Usage("`--part` must be 1 or 2.".into()));
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let path = config::get().paths.puzzle(day);
+
+    if refresh || !Path::new(&path).exists() {
+        aoc_cli::check()?;
+        aoc_cli::download_puzzle(day)?;
+    }
+
+    let puzzle = fs::read_to_string(&path).context(format!("Failed to read puzzle \"{path}\""))?;
+    let puzzle = match part {
+        Some(part) => part_section(&puzzle, part).ok_or_else(|| {
+            Error::Usage(format!(
+                "part two of day {day} is not unlocked yet. Once part one is solved, run `cargo read {day} --part 2 --refresh`."
+            ))
+        })?,
+        None => &puzzle,
     };
+
+    let width = RawTerminal::size().1.min(MAX_WIDTH);
+    page(&render(puzzle, width))
+}
+
+/// Shows `text` in `$PAGER` (`less` by default) if stdout is a terminal, prints it otherwise.
+fn page(text: &str) -> Result<()> {
+    if io::stdout().is_terminal() {
+        let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -RFX".into());
+        let mut args = pager.split_whitespace();
+
+        // fall back to printing if the pager is not installed.
+        if let Some(mut child) = args.next().and_then(|program| {
+            Command::new(program)
+                .args(args)
+                .stdin(Stdio::piped())
+                .spawn()
+                .ok()
+        }) {
+            if let Some(mut stdin) = child.stdin.take() {
+                // the user may quit the pager before it read everything.
+                stdin.write_all(text.as_bytes()).ok();
+            }
+            child.wait().context("Failed to wait for the pager")?;
+            return Ok(());
+        }
+    }
+
+    println!("{text}");
+    Ok(())
 }
@@ -1,14 +1,14 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
 };
 
+use crate::template::config;
+use crate::template::error::{Context, Result};
+use crate::template::input_shape::Shape;
+use crate::template::templates::{self, Placeholders};
 use crate::template::Day;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
-
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
@@ -19,61 +19,63 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Creates an empty file unless it exists, returns whether it was created.
+fn create_file_if_missing(path: &str) -> Result<bool, std::io::Error> {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
+pub fn handle(
+    day: Day,
+    overwrite: bool,
+    template: Option<&str>,
+    answer_type: Option<&str>,
+) -> Result<()> {
+    let config = config::get();
+    let input_path = config.paths.input(day);
+    let example_path = config.paths.example(day);
     let module_path = format!("src/bin/{day}.rs");
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    let template_name = template.or(config.template.as_deref());
+
+    // without a selected template, the parse function is generated from the shape of a downloaded input.
+    let shape = match template_name {
+        Some(_) => None,
+        None => Shape::detect(&fs::read_to_string(&input_path).unwrap_or_default()),
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+    let template = match shape {
+        Some(shape) => {
+            println!("Detected input shape: {}", shape.describe());
+            shape.template(&templates::load("default")?)
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
-    }
+        None => templates::load(template_name.unwrap_or("default"))?,
+    };
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    let answer_type = answer_type
+        .or(config.answer_type.as_deref())
+        .or(shape.and_then(Shape::answer_type));
+    let contents = Placeholders::new(day, answer_type).render(&template);
+
+    let mut file =
+        safe_create_file(&module_path, overwrite).context("Failed to create module file")?;
+
+    file.write_all(contents.as_bytes())
+        .context("Failed to write module contents")?;
+    println!("Created module file \"{}\"", &module_path);
+
+    if create_file_if_missing(&input_path).context("Failed to create input file")? {
+        println!("Created empty input file \"{}\"", &input_path);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    if create_file_if_missing(&example_path).context("Failed to create example file")? {
+        println!("Created empty example file \"{}\"", &example_path);
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
+    Ok(())
 }
@@ -1,8 +1,29 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::error::{Error, Result};
+use crate::template::watch::{parse_test_results, watch};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    threads: Option<usize>,
+    part: Option<u8>,
+    show_output: bool,
+    verbosity: u8,
+    explain: bool,
+    explain_json: Option<&str>,
+    profile_spans: bool,
+    watch_files: bool,
+    example: bool,
+) -> Result<()> {
+    if example {
+        return watch(day, || run_examples(day, release));
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -23,12 +44,129 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    if let Some(threads) = threads {
+        cmd_args.push("--threads".to_string());
+        cmd_args.push(threads.to_string());
+    }
+
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if show_output {
+        cmd_args.push("--show-output".to_string());
+    }
+
+    if verbosity > 0 {
+        if release || dhat {
+            eprintln!("Note: `debug!` and `trace!` are compiled out of optimized builds, drop `--release` to see them.");
+        }
+        cmd_args.push(if verbosity > 1 { "-vv" } else { "-v" }.to_string());
+    }
+
+    if let Some(explain_json) = explain_json {
+        cmd_args.push("--explain-json".to_string());
+        cmd_args.push(explain_json.to_string());
+    } else if explain {
+        cmd_args.push("--explain".to_string());
+    }
+
+    if profile_spans {
+        cmd_args.push("--profile-spans".to_string());
+    }
+
+    if watch_files {
+        cmd_args.insert(1, "--quiet".to_string());
+        return watch(day, || {
+            // keep watching if the solution fails to build or panics.
+            match run_solution(&cmd_args) {
+                Ok(()) => println!("\n{ANSI_BOLD}✓ ok{ANSI_RESET}"),
+                Err(Error::MissingTool { tool, hint }) => {
+                    return Err(Error::MissingTool { tool, hint })
+                }
+                Err(_) => println!("\n{ANSI_BOLD}✖ failed{ANSI_RESET}"),
+            }
+            Ok(())
+        });
+    }
+
+    run_solution(&cmd_args)
+}
+
+fn run_solution(cmd_args: &[String]) -> Result<()> {
+    let status = Command::new("cargo")
+        .args(cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(|_| missing_cargo())?;
+
+    // the solution binary reports its own errors, pass its exit code on.
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::from_child_code(status.code()))
+    }
+}
+
+/// Runs the tests of a day and prints a line per test, with the panic message of failed tests.
+fn run_examples(day: Day, release: bool) -> Result<()> {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["test", "--bin", &day.to_string()]);
+    if release {
+        cmd.arg("--release");
+    }
+
+    let output = cmd
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .map_err(|_| missing_cargo())?;
+
+    let lines = |bytes: &[u8]| -> Vec<String> {
+        String::from_utf8_lossy(bytes)
+            .lines()
+            .map(str::to_string)
+            .collect()
+    };
+    let results = parse_test_results(&lines(&output.stdout));
 
-    cmd.wait().unwrap();
+    // without results, the build failed: print the compiler errors without cargo's progress lines.
+    if results.is_empty() && !output.status.success() {
+        for line in lines(&output.stderr) {
+            let progress = ["Compiling ", "Finished ", "Running ", "Blocking "];
+            if !progress.iter().any(|x| line.trim_start().starts_with(x)) {
+                eprintln!("{line}");
+            }
+        }
+        println!("\n{ANSI_BOLD}✖ build failed{ANSI_RESET}");
+        return Ok(());
+    }
+
+    for result in &results {
+        let name = result.name.strip_prefix("tests::").unwrap_or(&result.name);
+        if result.passed {
+            println!("✓ {name}");
+        } else {
+            println!("✖ {ANSI_BOLD}{name}{ANSI_RESET}");
+            for line in &result.message {
+                println!("    {ANSI_ITALIC}{line}{ANSI_RESET}");
+            }
+        }
+    }
+
+    let failed = results.iter().filter(|x| !x.passed).count();
+    println!(
+        "\n{ANSI_BOLD}{} passed, {failed} failed{ANSI_RESET}",
+        results.len() - failed
+    );
+
+    Ok(())
+}
+
+fn missing_cargo() -> Error {
+    Error::MissingTool {
+        tool: "cargo",
+        hint: None,
+    }
 }
@@ -0,0 +1,34 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::{Timings, TimingsStore};
+use crate::template::{all_days, aoc_cli, Day, ANSI_BOLD, ANSI_RESET};
+
+/// Prints which of `days` are scaffolded, have an input and have stored benchmarks.
+pub fn handle(days: &HashSet<Day>) {
+    let store = TimingsStore::read_from_file();
+    let timings = store
+        .sets
+        .iter()
+        .fold(Timings::default(), |acc, t| acc.merge_mixed(t));
+
+    println!(
+        "{ANSI_BOLD}{:<6}{:<10}{:<8}{:>12}{:>12}{ANSI_RESET}",
+        "Day", "Solution", "Input", "Part 1", "Part 2"
+    );
+
+    for day in all_days().filter(|day| days.contains(day)) {
+        let check = |exists: bool| if exists { "✓" } else { "-" };
+        let timing = timings.data.iter().find(|t| t.day == day);
+
+        println!(
+            "{:<6}{:<10}{:<8}{:>12}{:>12}",
+            day.to_string(),
+            check(Path::new(&get_path_for_bin(day)).exists()),
+            check(Path::new(&aoc_cli::get_input_path(day)).exists()),
+            timing.and_then(|t| t.part_1.as_deref()).unwrap_or("-"),
+            timing.and_then(|t| t.part_2.as_deref()).unwrap_or("-"),
+        );
+    }
+}
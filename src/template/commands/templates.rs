@@ -0,0 +1,51 @@
+use crate::template::config;
+use crate::template::templates::{user_templates, BUILT_IN};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Prints the built-in and user templates that can be passed to `cargo scaffold --template`.
+pub fn handle() {
+    let config = config::get();
+    let default = config.template.as_deref().unwrap_or("default");
+    let user = user_templates();
+
+    let width = BUILT_IN
+        .iter()
+        .map(|t| t.name)
+        .chain(user.iter().map(String::as_str))
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+
+    let marker = |name: &str| if name == default { " (default)" } else { "" };
+
+    println!("{ANSI_BOLD}Built-in templates:{ANSI_RESET}");
+    for template in BUILT_IN {
+        println!(
+            "  {:<width$}  {}{}",
+            template.name,
+            template.about,
+            marker(template.name)
+        );
+    }
+
+    println!();
+    println!("{ANSI_BOLD}User templates:{ANSI_RESET}");
+    if user.is_empty() {
+        println!(
+            "  {ANSI_ITALIC}none, add `<name>.txt` files to \"{}\"{ANSI_RESET}",
+            config.templates
+        );
+    }
+    for name in &user {
+        let shadows = if BUILT_IN.iter().any(|t| t.name == name) {
+            ", replaces the built-in template"
+        } else {
+            ""
+        };
+        println!(
+            "  {name:<width$}  {}/{name}.txt{shadows}{}",
+            config.templates,
+            marker(name)
+        );
+    }
+}
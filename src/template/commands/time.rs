@@ -1,40 +1,279 @@
 use std::collections::HashSet;
+use std::io::{stdout, Write};
+use std::path::Path;
+use std::thread;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
-
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
-
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+use crate::template::error::{Error, Result};
+use crate::template::fingerprint::fingerprint;
+use crate::template::machine::Machine;
+use crate::template::noise::{check_system, pin_to_core, NoiseControl};
+use crate::template::run_multi::child_commands::{parse_exec_time, run_bench};
+use crate::template::run_multi::{run_multi_in, run_multi_interleaved, RunOptions};
+use crate::template::runner::BENCH_BUDGET_MILLIS;
+use crate::template::timings::{parse_duration, Timing, Timings, TimingsStore};
+use crate::template::worktree::Worktree;
+use crate::template::{all_days, readme_benchmarks, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    days: Option<HashSet<Day>>,
+    run_all: bool,
+    store: bool,
+    rev: Option<&str>,
+    merge_machines: bool,
+    noise_control: Option<NoiseControl>,
+    threads: Option<usize>,
+    part: Option<u8>,
+) -> Result<()> {
+    let options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        threads,
+        part,
+    };
+
+    // pin before detecting the machine, the available parallelism follows the CPU affinity.
+    if let Some(noise_control) = noise_control {
+        prepare_system(noise_control);
+    }
+
+    let mut timings_store = TimingsStore::read_from_file();
+    let machine = Machine::detect(true, threads);
+    let stored_timings = timings_store.get(Some(&machine));
+
+    let days_to_run = days.unwrap_or_else(|| {
+        // comparisons with another revision always cover all days.
+        if run_all || rev.is_some() {
+            all_days().collect()
+        } else {
+            // when the `--all` flag is not set, only bench days whose source or input changed.
+            select_stale_days(&stored_timings)
+        }
+    });
+
+    if days_to_run.is_empty() {
+        println!("All benchmarks are up to date. Pass a day or `--all` to bench anyway.");
+        return Ok(());
+    }
+
+    let baseline = match rev {
+        Some(rev) => {
+            let worktree = Worktree::checkout(rev).map_err(|e| {
+                Error::Other(format!("Failed to check out revision \"{rev}\": {e}"))
+            })?;
+
+            println!(
+                "{ANSI_BOLD}Benching revision {}{ANSI_RESET}",
+                worktree.label
+            );
+            println!();
+            let timings = bench(
+                Some(&worktree.path),
+                &days_to_run,
+                noise_control,
+                &options,
+                &machine,
+            )?;
+
+            println!();
+            println!("{ANSI_BOLD}Benching working tree{ANSI_RESET}");
+            println!();
+            Some((worktree.label.clone(), timings))
+        }
+        None => None,
+    };
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = bench(None, &days_to_run, noise_control, &options, &machine)?;
+
+    if let Some((label, baseline)) = baseline {
+        print_comparison(&label, &baseline, &timings);
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let table = timings_store.record(&timings, merge_machines)?;
+        timings_store.store_file()?;
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
+        readme_benchmarks::update(table)?;
+        println!("Stored updated benchmarks.");
+    }
+
+    Ok(())
+}
+
+fn bench(
+    workdir: Option<&Path>,
+    days_to_run: &HashSet<Day>,
+    noise_control: Option<NoiseControl>,
+    options: &RunOptions,
+    machine: &Machine,
+) -> Result<Timings> {
+    match noise_control {
+        Some(noise_control) => {
+            run_multi_interleaved(workdir, days_to_run, noise_control.rounds, options, machine)
+        }
+        None => Ok(run_multi_in(workdir, days_to_run, options, machine)?.unwrap_or_default()),
+    }
+}
+
+/// Benches a day with 1, 2, 4, ... up to `max_threads` rayon threads and prints the speedup over one thread.
+pub fn handle_scaling(day: Day, max_threads: Option<usize>, part: Option<u8>) -> Result<()> {
+    let max_threads = max_threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, std::num::NonZero::get));
+
+    let mut thread_counts: Vec<usize> = (0..)
+        .map(|x| 1 << x)
+        .take_while(|x| *x < max_threads)
+        .collect();
+    thread_counts.push(max_threads);
+
+    let mut results: Vec<(usize, Timing)> = vec![];
+    let mut stdout = stdout();
+
+    for threads in thread_counts {
+        print!("\rBenching day {day} with {threads} thread(s)...");
+        let _ = stdout.flush();
+
+        let options = RunOptions {
+            threads: Some(threads),
+            part,
+            ..RunOptions::default()
+        };
+
+        let output = run_bench(None, day, BENCH_BUDGET_MILLIS, &options)?;
+        if output.is_empty() {
+            println!();
+            return Err(Error::Solution(format!("day {day} is not solved.")));
+        }
+
+        results.push((threads, parse_exec_time(&output, day)));
+    }
+
+    println!("\r{}", " ".repeat(48));
+    println!(
+        "{ANSI_BOLD}{:>7}{:>12}{:>12}{:>12}{:>9}{:>12}{ANSI_RESET}",
+        "Threads", "Part 1", "Part 2", "Total", "Speedup", "Efficiency"
+    );
+
+    let base_nanos = results[0].1.total_nanos;
+
+    for (threads, timing) in &results {
+        let speedup = base_nanos / timing.total_nanos.max(1_f64);
+        #[allow(clippy::cast_precision_loss)]
+        let efficiency = speedup / *threads as f64 * 100_f64;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let bar = "█".repeat((speedup * 4_f64).round() as usize);
+
+        println!(
+            "{:>7}{:>12}{:>12}{:>12}{:>8.2}x{:>11.0}%  {ANSI_ITALIC}{bar}{ANSI_RESET}",
+            threads,
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-"),
+            format!("{:.2}ms", timing.total_nanos / 1_000_000_f64),
+            speedup,
+            efficiency
+        );
+    }
+
+    Ok(())
+}
+
+fn prepare_system(noise_control: NoiseControl) {
+    let core = noise_control.core();
+
+    match pin_to_core(core) {
+        Ok(()) => println!("Pinned benchmarks to core {core}."),
+        Err(e) => eprintln!("Warning: could not pin benchmarks to core {core}: {e}"),
+    }
+
+    for warning in check_system(core) {
+        eprintln!("Warning: {warning}");
+    }
+
+    println!();
+}
+
+fn select_stale_days(stored_timings: &Timings) -> HashSet<Day> {
+    let mut days = HashSet::new();
+
+    for day in all_days() {
+        // days that have not been scaffolded yet have no hash.
+        let Some(hash) = fingerprint(day) else {
+            continue;
+        };
+
+        if let Some(reason) = stored_timings.stale_reason(day, &hash) {
+            if days.is_empty() {
+                println!("{ANSI_BOLD}Selected days{ANSI_RESET}");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            println!("Day {day}: {reason}");
+            days.insert(day);
+        }
+    }
+
+    if !days.is_empty() {
+        println!();
+    }
+
+    days
+}
+
+fn print_comparison(label: &str, baseline: &Timings, current: &Timings) {
+    println!();
+    println!(
+        "{ANSI_BOLD}{:<10}{:>14}{:>14}{:>16}{ANSI_RESET}",
+        "", label, "working tree", "change"
+    );
+
+    for timing in &current.data {
+        let Some(base) = baseline.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        let parts = [
+            ("Part 1", &base.part_1, &timing.part_1),
+            ("Part 2", &base.part_2, &timing.part_2),
+        ];
+
+        println!("Day {}", timing.day);
+        for (part, before, after) in parts {
+            if before.is_none() && after.is_none() {
+                continue;
             }
+
+            let change = before
+                .as_deref()
+                .and_then(parse_duration)
+                .zip(after.as_deref().and_then(parse_duration))
+                .map_or_else(|| "-".into(), |(b, a)| format_change(b, a));
+
+            println!(
+                "  {part:<8}{:>14}{:>14}{:>16}",
+                before.as_deref().unwrap_or("-"),
+                after.as_deref().unwrap_or("-"),
+                change
+            );
         }
     }
+
+    let (total_before, total_after) = (baseline.total_millis(), current.total_millis());
+    println!(
+        "{ANSI_BOLD}{:<10}{ANSI_RESET}{:>14}{:>14}{ANSI_ITALIC}{:>16}{ANSI_RESET}",
+        "Total",
+        format!("{total_before:.2}ms"),
+        format!("{total_after:.2}ms"),
+        format_change(total_before, total_after)
+    );
+}
+
+fn format_change(before: f64, after: f64) -> String {
+    if before <= 0_f64 || after <= 0_f64 {
+        return "-".into();
+    }
+
+    if after <= before {
+        format!("{:.2}x faster", before / after)
+    } else {
+        format!("{:.2}x slower", after / before)
+    }
 }
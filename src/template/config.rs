@@ -0,0 +1,560 @@
+/// Project configuration, read once from `aoc.toml` in the project root.
+///
+/// ```toml
+/// year = 2024
+/// contact = "you@example.com"
+///
+/// [paths]
+/// data = "data"
+/// inputs = "data/inputs"
+/// examples = "data/examples"
+/// puzzles = "data/puzzles"
+/// timings = "data/timings.json"
+///
+/// [session]
+/// file = "~/.adventofcode.session"
+///
+/// [scaffold]
+/// template = "grid"
+/// templates = "templates"
+/// answer_type = "u64"
+///
+/// [benchmarks]
+/// file = "README.md"
+/// columns = ["part1", "part2", "total"]
+/// budget = "100ms"
+/// ```
+///
+/// Every key is optional, the defaults match the layout of the template.
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::{env, fs, io};
+
+use crate::template::error::{Error, Result};
+use crate::template::readme_benchmarks::{SortOrder, TableConfig};
+use crate::template::timings::parse_duration;
+use crate::template::Day;
+
+pub const CONFIG_FILE_PATH: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Puzzle year passed to aoc-cli. `AOC_YEAR` takes precedence if set.
+    pub year: Option<u16>,
+    /// Contact of the user, e.g. an email address, added to the User-Agent of bulk downloads.
+    pub contact: Option<String>,
+    pub paths: Paths,
+    pub session: Session,
+    /// Name or path of the template `cargo scaffold` uses if `--template` is not passed.
+    pub template: Option<String>,
+    /// Directory of user templates.
+    pub templates: String,
+    /// Answer type of the parts in scaffolded solutions.
+    pub answer_type: Option<String>,
+    pub benchmarks: TableConfig,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Paths {
+    /// Parent directory of data folders without a dedicated key.
+    pub data: String,
+    pub inputs: String,
+    pub examples: String,
+    pub puzzles: String,
+    pub timings: String,
+}
+
+/// Where aoc-cli reads the session token from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Session {
+    /// aoc-cli's own lookup: `ADVENT_OF_CODE_SESSION` or `~/.adventofcode.session`.
+    Default,
+    /// A file that contains the token.
+    File(String),
+    /// An environment variable that contains the token.
+    Env(String),
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            contact: None,
+            paths: Paths::under("data"),
+            session: Session::Default,
+            template: None,
+            templates: "templates".into(),
+            answer_type: None,
+            benchmarks: TableConfig::default(),
+        }
+    }
+}
+
+impl Paths {
+    fn under(data: &str) -> Self {
+        Self {
+            data: data.into(),
+            inputs: format!("{data}/inputs"),
+            examples: format!("{data}/examples"),
+            puzzles: format!("{data}/puzzles"),
+            timings: format!("{data}/timings.json"),
+        }
+    }
+
+    /// Returns the directory of a data folder, e.g. `inputs` or `examples`.
+    pub fn folder(&self, folder: &str) -> String {
+        match folder {
+            "inputs" => self.inputs.clone(),
+            "examples" => self.examples.clone(),
+            "puzzles" => self.puzzles.clone(),
+            _ => format!("{}/{folder}", self.data),
+        }
+    }
+
+    pub fn input(&self, day: Day) -> String {
+        format!("{}/{day}.txt", self.inputs)
+    }
+
+    pub fn example(&self, day: Day) -> String {
+        format!("{}/{day}.txt", self.examples)
+    }
+
+    pub fn puzzle(&self, day: Day) -> String {
+        format!("{}/{day}.md", self.puzzles)
+    }
+}
+
+/// Returns the project configuration, exits if `aoc.toml` is invalid.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| Config::load().unwrap_or_else(|e| e.exit()))
+}
+
+impl Config {
+    /// Reads `aoc.toml` from the current directory, falling back to defaults if it does not exist.
+    fn load() -> Result<Self> {
+        let mut config = match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(s) => Self::parse(&s)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                return Err(Error::Io {
+                    context: format!("Failed to read \"{CONFIG_FILE_PATH}\""),
+                    source: e,
+                })
+            }
+        };
+
+        if let Some(year) = env::var("AOC_YEAR").ok().and_then(|x| x.parse().ok()) {
+            config.year = Some(year);
+        }
+
+        Ok(config)
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut values = parse_toml(s).map_err(|e| config_error(&e))?;
+        let mut config = Self::default();
+
+        if let Some(year) = values.remove("year") {
+            config.year = Some(
+                year.as_int()
+                    .and_then(|x| u16::try_from(x).ok())
+                    .ok_or_else(|| config_error("`year` must be a number"))?,
+            );
+        }
+
+        config.contact = take_string(&mut values, "contact")?;
+
+        if let Some(data) = take_string(&mut values, "paths.data")? {
+            config.paths = Paths::under(&data);
+        }
+        for (key, path) in [
+            ("paths.inputs", &mut config.paths.inputs),
+            ("paths.examples", &mut config.paths.examples),
+            ("paths.puzzles", &mut config.paths.puzzles),
+            ("paths.timings", &mut config.paths.timings),
+        ] {
+            if let Some(value) = take_string(&mut values, key)? {
+                *path = value;
+            }
+        }
+
+        config.session = match (
+            take_string(&mut values, "session.file")?,
+            take_string(&mut values, "session.env")?,
+        ) {
+            (Some(_), Some(_)) => {
+                return Err(config_error(
+                    "`session.file` and `session.env` are mutually exclusive",
+                ))
+            }
+            (Some(file), None) => Session::File(expand_home(&file)),
+            (None, Some(var)) => Session::Env(var),
+            (None, None) => Session::Default,
+        };
+
+        config.template = take_string(&mut values, "scaffold.template")?;
+        if let Some(templates) = take_string(&mut values, "scaffold.templates")? {
+            config.templates = templates;
+        }
+        config.answer_type = take_string(&mut values, "scaffold.answer_type")?;
+
+        parse_benchmarks(&mut values, &mut config.benchmarks)?;
+
+        if let Some(key) = values.keys().min() {
+            return Err(config_error(&format!("unknown key `{key}`")));
+        }
+
+        Ok(config)
+    }
+}
+
+fn parse_benchmarks(values: &mut HashMap<String, Value>, table: &mut TableConfig) -> Result<()> {
+    if let Some(path) = take_string(values, "benchmarks.file")? {
+        table.path = path;
+    }
+
+    if let Some(marker) = take_string(values, "benchmarks.marker")? {
+        table.marker = marker;
+    }
+
+    if let Some(level) = values.remove("benchmarks.heading") {
+        table.heading_level = match level.as_int() {
+            Some(x @ 1..=6) => usize::try_from(x).unwrap_or(2),
+            _ => {
+                return Err(config_error(
+                    "`benchmarks.heading` must be a number between 1 and 6",
+                ))
+            }
+        };
+    }
+
+    if let Some(columns) = values.remove("benchmarks.columns") {
+        let Value::Array(columns) = columns else {
+            return Err(config_error("`benchmarks.columns` must be an array"));
+        };
+        table.columns = columns
+            .iter()
+            .map(|c| match c {
+                Value::String(s) => s.parse(),
+                _ => Err(config_error("`benchmarks.columns` must contain strings")),
+            })
+            .collect::<Result<_>>()?;
+    }
+
+    if let Some(sort) = take_string(values, "benchmarks.sort")? {
+        table.sort = match sort.as_str() {
+            "day" => SortOrder::Day,
+            "slowest" => SortOrder::Slowest,
+            x => return Err(config_error(&format!("unknown benchmark sort order `{x}`"))),
+        };
+    }
+
+    if let Some(unit) = take_string(values, "benchmarks.unit")? {
+        table.unit = unit.parse()?;
+    }
+
+    if let Some(budget) = take_string(values, "benchmarks.budget")? {
+        table.budget_nanos = Some(
+            parse_duration(&budget)
+                .ok_or_else(|| config_error(&format!("could not parse budget `{budget}`")))?,
+        );
+    }
+
+    Ok(())
+}
+
+fn config_error(message: &str) -> Error {
+    Error::Usage(format!("{CONFIG_FILE_PATH}: {message}."))
+}
+
+fn take_string(values: &mut HashMap<String, Value>, key: &str) -> Result<Option<String>> {
+    match values.remove(key) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(_) => Err(config_error(&format!("`{key}` must be a string"))),
+    }
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), env::var("HOME")) {
+        (Some(rest), Ok(home)) => Path::new(&home).join(rest).to_string_lossy().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// The values of the TOML subset that `aoc.toml` is written in.
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    fn as_int(&self) -> Option<i64> {
+        match self {
+            Value::Integer(x) => Some(*x),
+            _ => None,
+        }
+    }
+}
+
+/// Parses `[table]` headers and single-line `key = value` pairs into a map of dotted keys.
+/// Supported values are strings, integers, booleans and arrays of those.
+fn parse_toml(s: &str) -> Result<HashMap<String, Value>, String> {
+    let mut values = HashMap::new();
+    let mut table = String::new();
+
+    for (index, line) in s.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        let line_error = |message: &str| format!("line {}: {message}", index + 1);
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let name = header
+                .strip_suffix(']')
+                .map(str::trim)
+                .filter(|name| is_key(name))
+                .ok_or_else(|| line_error("invalid table header"))?;
+            table = format!("{name}.");
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| line_error("expected `key = value`"))?;
+
+        let key = key.trim();
+        if !is_key(key) {
+            return Err(line_error(&format!("invalid key `{key}`")));
+        }
+
+        let (value, rest) = parse_value(value.trim()).map_err(|e| line_error(&e))?;
+        if !rest.trim().is_empty() {
+            return Err(line_error("unexpected characters after value"));
+        }
+
+        if values.insert(format!("{table}{key}"), value).is_some() {
+            return Err(line_error(&format!("duplicate key `{key}`")));
+        }
+    }
+
+    Ok(values)
+}
+
+fn is_key(s: &str) -> bool {
+    !s.is_empty()
+        && s.split('.').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+}
+
+/// Removes a trailing `# comment` that is not part of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+
+    line
+}
+
+/// Parses the value at the start of `s`, returns it and the remaining input.
+fn parse_value(s: &str) -> Result<(Value, &str), String> {
+    if let Some(rest) = s.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return Ok((Value::String(value), &rest[i + 1..])),
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    x => {
+                        return Err(format!(
+                            "unsupported escape sequence `\\{}`",
+                            x.unwrap_or(' ')
+                        ))
+                    }
+                },
+                c => value.push(c),
+            }
+        }
+
+        return Err("unterminated string".into());
+    }
+
+    if let Some(rest) = s.strip_prefix('\'') {
+        let end = rest.find('\'').ok_or("unterminated string")?;
+        return Ok((Value::String(rest[..end].into()), &rest[end + 1..]));
+    }
+
+    if let Some(mut rest) = s.strip_prefix('[') {
+        let mut items = vec![];
+
+        loop {
+            rest = rest.trim_start();
+            if let Some(rest) = rest.strip_prefix(']') {
+                return Ok((Value::Array(items), rest));
+            }
+
+            let (item, next) = parse_value(rest)?;
+            items.push(item);
+
+            rest = next.trim_start();
+            if let Some(next) = rest.strip_prefix(',') {
+                rest = next;
+            } else if !rest.starts_with(']') {
+                return Err("expected `,` or `]` in array".into());
+            }
+        }
+    }
+
+    let end = s.find([',', ']', ' ']).unwrap_or(s.len());
+    let (token, rest) = s.split_at(end);
+
+    let value = match token {
+        "true" => Value::Boolean(true),
+        "false" => Value::Boolean(false),
+        x => Value::Integer(
+            x.replace('_', "")
+                .parse()
+                .map_err(|_| format!("unsupported value `{x}`"))?,
+        ),
+    };
+
+    Ok((value, rest))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_toml, Config, Paths, Session, Value};
+    use crate::day;
+    use crate::template::readme_benchmarks::{Column, SortOrder, TableConfig};
+
+    #[test]
+    fn defaults_match_template_layout() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.year, None);
+        assert_eq!(config.contact, None);
+        assert_eq!(config.paths.input(day!(1)), "data/inputs/01.txt");
+        assert_eq!(config.paths.example(day!(1)), "data/examples/01.txt");
+        assert_eq!(config.paths.puzzle(day!(1)), "data/puzzles/01.md");
+        assert_eq!(config.paths.timings, "data/timings.json");
+        assert_eq!(config.session, Session::Default);
+        assert_eq!(config.benchmarks.path, TableConfig::default().path);
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = Config::parse(
+            r#"
+            year = 2023 # the year to solve
+            contact = "me@example.com"
+
+            [paths]
+            data = "aoc"
+            examples = "tests/examples"
+
+            [session]
+            env = "AOC_TOKEN"
+
+            [benchmarks]
+            file = 'BENCHMARKS.md'
+            heading = 3
+            columns = ["part1", "total"]
+            sort = "slowest"
+            budget = "100ms"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.contact.as_deref(), Some("me@example.com"));
+        assert_eq!(
+            config.paths,
+            Paths {
+                data: "aoc".into(),
+                inputs: "aoc/inputs".into(),
+                examples: "tests/examples".into(),
+                puzzles: "aoc/puzzles".into(),
+                timings: "aoc/timings.json".into(),
+            }
+        );
+        assert_eq!(config.paths.folder("examples"), "tests/examples");
+        assert_eq!(config.paths.folder("solutions"), "aoc/solutions");
+        assert_eq!(config.session, Session::Env("AOC_TOKEN".into()));
+        assert_eq!(config.benchmarks.path, "BENCHMARKS.md");
+        assert_eq!(config.benchmarks.heading_level, 3);
+        assert_eq!(
+            config.benchmarks.columns,
+            vec![Column::Part1, Column::Total]
+        );
+        assert_eq!(config.benchmarks.sort, SortOrder::Slowest);
+        assert_eq!(config.benchmarks.budget_nanos, Some(1e8));
+    }
+
+    #[test]
+    fn parses_toml_values() {
+        let values =
+            parse_toml("a = \"x # not a comment \\\"\"\n[t]\nb = [1, true, 'y',]\nc = 1_000")
+                .unwrap();
+
+        assert_eq!(values["a"], Value::String("x # not a comment \"".into()));
+        assert_eq!(
+            values["t.b"],
+            Value::Array(vec![
+                Value::Integer(1),
+                Value::Boolean(true),
+                Value::String("y".into())
+            ])
+        );
+        assert_eq!(values["t.c"], Value::Integer(1000));
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        for s in [
+            "year = \"2024\"",
+            "yeer = 2024",
+            "[paths]\ninputs = 1",
+            "[benchmarks]\nheading = 7",
+            "[benchmarks]\ncolumns = [\"part3\"]",
+            "[session]\nfile = \"a\"\nenv = \"b\"",
+            "[paths\ninputs = \"x\"",
+            "a = \"unterminated",
+            "a = 1\na = 2",
+            "a = 1 2",
+        ] {
+            assert!(Config::parse(s).is_err(), "`{s}` should not parse");
+        }
+
+        let err = Config::parse("\n\nyear 2024").unwrap_err().to_string();
+        assert_eq!(err, "aoc.toml: line 3: expected `key = value`.");
+    }
+}
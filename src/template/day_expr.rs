@@ -0,0 +1,235 @@
+/// Day expressions that select a set of days for commands that operate on multiple days.
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::config;
+use crate::template::timings::{Timings, TimingsStore};
+use crate::template::{all_days, Day};
+
+/// Days whose stored total time exceeds this are `slow`, unless a budget is set in `aoc.toml`.
+const DEFAULT_SLOW_NANOS: f64 = 100_000_000_f64;
+
+/// A comma-separated list of day terms, resolved to the union of the days they select.
+///
+/// Supported terms are single days (`7`), inclusive ranges (`1-5`, `..10`, `20..`) and the keywords
+/// `all`, `unsolved` (days without stored timings for both parts) and `slow` (days whose stored timings
+/// exceed the benchmark budget).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayExpr {
+    terms: Vec<Term>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Term {
+    Range(Day, Day),
+    Unsolved,
+    Slow,
+}
+
+impl DayExpr {
+    /// Resolves the expression against the stored benchmarks.
+    pub fn resolve(&self) -> HashSet<Day> {
+        // a day counts as solved if it was benched on any machine.
+        let store = TimingsStore::read_from_file();
+        let timings = store
+            .sets
+            .iter()
+            .fold(Timings::default(), |acc, t| acc.merge_mixed(t));
+
+        let slow_nanos = config::get()
+            .benchmarks
+            .budget_nanos
+            .unwrap_or(DEFAULT_SLOW_NANOS);
+
+        self.resolve_with(&timings, slow_nanos)
+    }
+
+    fn resolve_with(&self, timings: &Timings, slow_nanos: f64) -> HashSet<Day> {
+        let mut days = HashSet::new();
+
+        for term in &self.terms {
+            match term {
+                Term::Range(start, end) => {
+                    days.extend(all_days().filter(|day| day >= start && day <= end));
+                }
+                Term::Unsolved => {
+                    days.extend(all_days().filter(|day| !timings.is_day_complete(*day)));
+                }
+                Term::Slow => days.extend(
+                    timings
+                        .data
+                        .iter()
+                        .filter(|t| t.total_nanos > slow_nanos)
+                        .map(|t| t.day),
+                ),
+            }
+        }
+
+        days
+    }
+}
+
+impl FromStr for DayExpr {
+    type Err = DayExprError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let terms = s
+            .split(',')
+            .map(|term| parse_term(term.trim()))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { terms })
+    }
+}
+
+fn parse_term(s: &str) -> Result<Term, DayExprError> {
+    let parse_day = |x: &str| {
+        x.trim()
+            .parse::<Day>()
+            .map_err(|_| DayExprError(format!("`{x}` is not a day between 1 and 25")))
+    };
+
+    let range = if let Some((start, end)) = s.split_once("..") {
+        let start = if start.is_empty() {
+            Day::__new_unchecked(1)
+        } else {
+            parse_day(start)?
+        };
+        let end = if end.is_empty() {
+            Day::__new_unchecked(25)
+        } else {
+            parse_day(end)?
+        };
+        (start, end)
+    } else if let Some((start, end)) = s.split_once('-') {
+        (parse_day(start)?, parse_day(end)?)
+    } else {
+        match s {
+            "" => return Err(DayExprError("empty day expression".into())),
+            "all" => (Day::__new_unchecked(1), Day::__new_unchecked(25)),
+            "unsolved" => return Ok(Term::Unsolved),
+            "slow" => return Ok(Term::Slow),
+            _ => {
+                let day = parse_day(s)?;
+                (day, day)
+            }
+        }
+    };
+
+    if range.0 > range.1 {
+        return Err(DayExprError(format!(
+            "range `{s}` is empty, its start is after its end"
+        )));
+    }
+
+    Ok(Term::Range(range.0, range.1))
+}
+
+/// An error which can be returned when parsing a [`DayExpr`].
+#[derive(Debug)]
+pub struct DayExprError(String);
+
+impl Error for DayExprError {}
+
+impl Display for DayExprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}. Expected days like `5`, `1-5`, `3,7,12`, `..10`, `unsolved` or `slow`",
+            self.0
+        )
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::DayExpr;
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+        template::Day,
+    };
+
+    fn resolve(s: &str) -> Vec<u8> {
+        let timings = Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    part_2: Some("20ms".into()),
+                    total_nanos: 3e7,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("1s".into()),
+                    part_2: Some("1s".into()),
+                    total_nanos: 2e9,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
+                },
+                Timing {
+                    day: day!(3),
+                    part_1: Some("1ms".into()),
+                    part_2: None,
+                    total_nanos: 1e6,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
+                },
+            ],
+            machine: None,
+        };
+
+        let days: HashSet<Day> = s.parse::<DayExpr>().unwrap().resolve_with(&timings, 1e8);
+        let mut days: Vec<u8> = days.into_iter().map(Day::into_inner).collect();
+        days.sort_unstable();
+        days
+    }
+
+    #[test]
+    fn resolves_days_and_ranges() {
+        assert_eq!(resolve("7"), vec![7]);
+        assert_eq!(resolve("1-5"), vec![1, 2, 3, 4, 5]);
+        assert_eq!(resolve("3,7,12"), vec![3, 7, 12]);
+        assert_eq!(resolve("..3"), vec![1, 2, 3]);
+        assert_eq!(resolve("23.."), vec![23, 24, 25]);
+        assert_eq!(resolve("4, 1-2,2"), vec![1, 2, 4]);
+        assert_eq!(resolve("all").len(), 25);
+    }
+
+    #[test]
+    fn resolves_keywords() {
+        assert_eq!(resolve("slow"), vec![2]);
+        assert_eq!(resolve("unsolved").len(), 23);
+        assert!(!resolve("unsolved").contains(&1));
+        assert!(resolve("unsolved").contains(&3));
+        assert_eq!(resolve("slow,1"), vec![1, 2]);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for s in ["", "0", "26", "5-3", "1-30", "1,,2", "fast", "1-", "x..3"] {
+            assert!(s.parse::<DayExpr>().is_err(), "`{s}` should not parse");
+        }
+    }
+
+    #[test]
+    fn explains_errors() {
+        let err = "5-3".parse::<DayExpr>().unwrap_err().to_string();
+        assert!(err.starts_with("range `5-3` is empty"));
+
+        let err = "1-30".parse::<DayExpr>().unwrap_err().to_string();
+        assert!(err.starts_with("`30` is not a day between 1 and 25"));
+    }
+}
@@ -0,0 +1,178 @@
+/// The error type shared by all template commands, and the process exit codes it maps to.
+use std::fmt::Display;
+use std::{io, process};
+
+use crate::template::Day;
+
+/// Process exit codes of the template commands.
+///
+/// | Code | Meaning |
+/// | --- | --- |
+/// | 0 | Success |
+/// | 1 | Any other failure, e.g. a file could not be written |
+/// | 2 | Invalid command-line input |
+/// | 3 | A required tool (`aoc`, `git`, `cargo`) is not installed or not callable |
+/// | 4 | A request to adventofcode.com failed or was rate-limited |
+/// | 5 | A solution failed to build or run, or its variants disagree on the answer |
+/// | 6 | A submitted answer was rejected |
+pub mod exit_code {
+    pub const FAILURE: i32 = 1;
+    pub const USAGE: i32 = 2;
+    pub const MISSING_TOOL: i32 = 3;
+    pub const NETWORK: i32 = 4;
+    pub const SOLUTION: i32 = 5;
+    pub const WRONG_ANSWER: i32 = 6;
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// Invalid command-line input or configuration.
+    Usage(String),
+    /// An external command is not installed or could not be called.
+    MissingTool {
+        tool: &'static str,
+        hint: Option<&'static str>,
+    },
+    /// A request to adventofcode.com failed or was rate-limited.
+    Network(String),
+    /// A solution failed to build or run.
+    Solution(String),
+    /// adventofcode.com rejected a submitted answer.
+    WrongAnswer { day: Day, part: u8 },
+    /// A child process already reported its error, only its exit code is passed on.
+    Exited(i32),
+    /// An I/O operation failed, `context` describes what was attempted.
+    Io { context: String, source: io::Error },
+    /// Any other failure, e.g. malformed stored data or a failing git command.
+    Other(String),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => exit_code::USAGE,
+            Error::MissingTool { .. } => exit_code::MISSING_TOOL,
+            Error::Network(_) => exit_code::NETWORK,
+            Error::Solution(_) => exit_code::SOLUTION,
+            Error::WrongAnswer { .. } => exit_code::WRONG_ANSWER,
+            Error::Exited(code) => *code,
+            Error::Io { .. } | Error::Other(_) => exit_code::FAILURE,
+        }
+    }
+
+    /// Prints the error and exits the process with its exit code.
+    pub fn exit(&self) -> ! {
+        if !matches!(self, Error::Exited(_)) {
+            eprintln!("Error: {self}");
+        }
+        process::exit(self.exit_code())
+    }
+
+    /// Maps the exit code of a child process that reports its own errors, e.g. a solution binary.
+    /// Codes outside of [`exit_code`], e.g. of panics or build failures, are reported as solution failures.
+    pub fn from_child_code(code: Option<i32>) -> Self {
+        match code {
+            Some(code @ exit_code::USAGE..=exit_code::WRONG_ANSWER) => Error::Exited(code),
+            _ => Error::Exited(exit_code::SOLUTION),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(message) | Error::Solution(message) | Error::Other(message) => {
+                write!(f, "{message}")
+            }
+            Error::MissingTool { tool, hint } => {
+                write!(f, "command \"{tool}\" not found or not callable.")?;
+                match hint {
+                    Some(hint) => write!(f, " {hint}"),
+                    None => Ok(()),
+                }
+            }
+            Error::Network(message) => write!(f, "{message}"),
+            Error::WrongAnswer { day, part } => {
+                write!(f, "the answer to day {day}, part {part} was rejected.")
+            }
+            Error::Exited(code) => write!(f, "child process exited with code {code}."),
+            Error::Io { context, source } => write!(f, "{context}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Adds a description of the attempted operation to I/O errors.
+pub trait Context<T> {
+    fn context(self, context: impl Into<String>) -> Result<T>;
+}
+
+impl<T> Context<T> for io::Result<T> {
+    fn context(self, context: impl Into<String>) -> Result<T> {
+        self.map_err(|source| Error::Io {
+            context: context.into(),
+            source,
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io;
+
+    use super::{exit_code, Context, Error};
+    use crate::day;
+
+    #[test]
+    fn maps_exit_codes() {
+        assert_eq!(Error::Usage("x".into()).exit_code(), exit_code::USAGE);
+        assert_eq!(
+            Error::MissingTool {
+                tool: "aoc",
+                hint: None
+            }
+            .exit_code(),
+            exit_code::MISSING_TOOL
+        );
+        assert_eq!(Error::Network("x".into()).exit_code(), exit_code::NETWORK);
+        assert_eq!(
+            Error::WrongAnswer {
+                day: day!(1),
+                part: 2
+            }
+            .exit_code(),
+            exit_code::WRONG_ANSWER
+        );
+        assert_eq!(Error::Other("x".into()).exit_code(), exit_code::FAILURE);
+    }
+
+    #[test]
+    fn maps_child_codes() {
+        assert_eq!(Error::from_child_code(Some(6)).exit_code(), 6);
+        assert_eq!(Error::from_child_code(Some(101)).exit_code(), 5);
+        assert_eq!(Error::from_child_code(None).exit_code(), 5);
+    }
+
+    #[test]
+    fn adds_context() {
+        let result: io::Result<()> = Err(io::ErrorKind::NotFound.into());
+        let error = result
+            .context("failed to read \"data/timings.json\"")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to read \"data/timings.json\": entity not found"
+        );
+        assert_eq!(error.exit_code(), exit_code::FAILURE);
+    }
+}
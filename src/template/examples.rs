@@ -0,0 +1,99 @@
+/// Example inputs and the `example_tests!` macro that checks solutions against them.
+use std::fs;
+use std::path::PathBuf;
+
+use crate::template::{config, Day};
+
+/// Returns the path of the `n`-th example of a day: `05.txt` for the first example, `05-2.txt` for the second.
+#[must_use]
+pub fn example_path(day: Day, n: u8) -> PathBuf {
+    let file = if n == 1 {
+        format!("{day}.txt")
+    } else {
+        format!("{day}-{n}.txt")
+    };
+
+    PathBuf::from(&config::get().paths.examples).join(file)
+}
+
+/// Reads the `n`-th example of a day, panics with its path if it does not exist.
+#[must_use]
+pub fn read_example(day: Day, n: u8) -> String {
+    let path = example_path(day, n);
+    fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("could not open example file \"{}\": {e}", path.display()))
+}
+
+/// Generates one test per part and example file, comparing the part's answer to the expected one:
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     advent_of_code::example_tests! {
+///         part_one: [1 => 143, 2 => 55],
+///         part_two: [1 => 123],
+///     }
+/// }
+/// ```
+///
+/// Example `1` is read from `data/examples/05.txt`, example `n` from `data/examples/05-n.txt`. The tests are
+/// named after the part and example, e.g. `tests::part_one::example_2`, and name the example file on failure.
+/// Expected answers can be values like `143` or options like `None`.
+#[macro_export]
+macro_rules! example_tests {
+    ($($part:ident: [$($example:tt => $expected:expr),* $(,)?]),* $(,)?) => {
+        $(
+            mod $part {
+                use super::*;
+
+                $($crate::example_tests!(@test $part, $example, $expected);)*
+            }
+        )*
+    };
+
+    // NOTE: test names can not be concatenated from the example number, every supported number needs an arm.
+    (@test $part:ident, 1, $expected:expr) => { $crate::example_tests!(@emit $part, example_1, 1, $expected); };
+    (@test $part:ident, 2, $expected:expr) => { $crate::example_tests!(@emit $part, example_2, 2, $expected); };
+    (@test $part:ident, 3, $expected:expr) => { $crate::example_tests!(@emit $part, example_3, 3, $expected); };
+    (@test $part:ident, 4, $expected:expr) => { $crate::example_tests!(@emit $part, example_4, 4, $expected); };
+    (@test $part:ident, 5, $expected:expr) => { $crate::example_tests!(@emit $part, example_5, 5, $expected); };
+    (@test $part:ident, 6, $expected:expr) => { $crate::example_tests!(@emit $part, example_6, 6, $expected); };
+    (@test $part:ident, 7, $expected:expr) => { $crate::example_tests!(@emit $part, example_7, 7, $expected); };
+    (@test $part:ident, 8, $expected:expr) => { $crate::example_tests!(@emit $part, example_8, 8, $expected); };
+    (@test $part:ident, 9, $expected:expr) => { $crate::example_tests!(@emit $part, example_9, 9, $expected); };
+    (@test $part:ident, $example:tt, $expected:expr) => {
+        compile_error!(concat!("example_tests! supports examples 1 to 9, got `", stringify!($example), "`"));
+    };
+
+    (@emit $part:ident, $name:ident, $example:literal, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let path = $crate::template::examples::example_path(DAY, $example);
+            let result = $part(&$crate::template::examples::read_example(DAY, $example));
+            let expected: Option<_> = ::core::convert::Into::into($expected);
+            assert_eq!(result, expected, "example file \"{}\"", path.display());
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::example_path;
+    use crate::day;
+
+    #[test]
+    fn names_example_files() {
+        assert_eq!(
+            example_path(day!(5), 1),
+            PathBuf::from("data/examples/05.txt")
+        );
+        assert_eq!(
+            example_path(day!(5), 2),
+            PathBuf::from("data/examples/05-2.txt")
+        );
+    }
+}
@@ -0,0 +1,291 @@
+/// Structured events that solutions emit to explain how they arrived at an answer, see the `explain!` macro.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::{env, fs};
+
+use tinyjson::JsonValue;
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Number of rows per event that are printed in table mode.
+const TABLE_ROWS: usize = 20;
+
+/// How collected events are reported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Print a table per event below the result of each part (`--explain`).
+    Table,
+    /// Write all events to a JSON file after the run (`--explain-json <path>`).
+    Json(String),
+}
+
+/// A named record of key/value pairs emitted by a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    pub part: u8,
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+static MODE: OnceLock<Option<Mode>> = OnceLock::new();
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static PART: AtomicU8 = AtomicU8::new(0);
+static EVENTS: Mutex<Vec<Event>> = Mutex::new(vec![]);
+
+fn mode() -> Option<&'static Mode> {
+    MODE.get_or_init(|| parse_mode(&env::args().collect::<Vec<_>>()))
+        .as_ref()
+}
+
+fn parse_mode(args: &[String]) -> Option<Mode> {
+    if let Some(index) = args.iter().position(|x| x == "--explain-json") {
+        return args.get(index + 1).map(|path| Mode::Json(path.clone()));
+    }
+
+    args.iter().any(|x| x == "--explain").then_some(Mode::Table)
+}
+
+/// Whether events are currently recorded. Checked by `explain!` before its fields are evaluated.
+#[inline]
+pub fn enabled() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Starts recording the events of `part`, discarding events of previous runs of the same part.
+pub fn start(part: u8) {
+    if mode().is_none() {
+        return;
+    }
+
+    EVENTS.lock().unwrap().retain(|e| e.part != part);
+    PART.store(part, Ordering::Relaxed);
+    ACTIVE.store(true, Ordering::Relaxed);
+}
+
+/// Stops recording, e.g. before a part is benched.
+pub fn stop() {
+    ACTIVE.store(false, Ordering::Relaxed);
+}
+
+#[doc(hidden)]
+pub fn record(name: &'static str, fields: Vec<(&'static str, String)>) {
+    let part = PART.load(Ordering::Relaxed);
+    EVENTS.lock().unwrap().push(Event { part, name, fields });
+}
+
+/// Prints the events of `part` as tables if running with `--explain`.
+pub fn print_part(part: u8) {
+    if mode() != Some(&Mode::Table) {
+        return;
+    }
+
+    let events = EVENTS.lock().unwrap();
+    let events: Vec<&Event> = events.iter().filter(|e| e.part == part).collect();
+
+    for line in format_tables(&events) {
+        println!("{line}");
+    }
+}
+
+/// Writes the events of all parts to a file if running with `--explain-json <path>`.
+pub fn finish() {
+    let Some(Mode::Json(path)) = mode() else {
+        return;
+    };
+
+    let json = to_json(&EVENTS.lock().unwrap());
+    let result = json
+        .format()
+        .map_err(|e| e.to_string())
+        .and_then(|s| fs::write(path, s).map_err(|e| e.to_string()));
+
+    match result {
+        Ok(()) => println!("Wrote explanation to \"{path}\"."),
+        Err(e) => eprintln!("Failed to write explanation to \"{path}\": {e}"),
+    }
+}
+
+/// Formats events as one table per event name, in order of first occurrence.
+fn format_tables(events: &[&Event]) -> Vec<String> {
+    let mut names: Vec<&str> = vec![];
+    for event in events {
+        if !names.contains(&event.name) {
+            names.push(event.name);
+        }
+    }
+
+    let mut lines = vec![];
+
+    for name in names {
+        let rows: Vec<&&Event> = events.iter().filter(|e| e.name == name).collect();
+
+        let mut keys: Vec<&str> = vec![];
+        for event in &rows {
+            for (key, _) in &event.fields {
+                if !keys.contains(key) {
+                    keys.push(key);
+                }
+            }
+        }
+
+        let cell = |event: &Event, key: &str| {
+            event
+                .fields
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map_or("-", |(_, v)| v.as_str())
+                .to_string()
+        };
+
+        let widths: Vec<usize> = keys
+            .iter()
+            .map(|key| {
+                rows.iter()
+                    .take(TABLE_ROWS)
+                    .map(|e| cell(e, key).chars().count())
+                    .chain([key.len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let join = |cells: Vec<String>| {
+            cells
+                .iter()
+                .zip(&widths)
+                .map(|(c, w)| format!("{c:<w$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+
+        lines.push(format!(
+            "  {ANSI_BOLD}{name}{ANSI_RESET} {ANSI_ITALIC}({} event{}){ANSI_RESET}",
+            rows.len(),
+            if rows.len() == 1 { "" } else { "s" }
+        ));
+        lines.push(format!(
+            "  {ANSI_ITALIC}{}{ANSI_RESET}",
+            join(keys.iter().map(ToString::to_string).collect())
+        ));
+
+        for event in rows.iter().take(TABLE_ROWS) {
+            lines.push(format!(
+                "  {}",
+                join(keys.iter().map(|k| cell(event, k)).collect())
+            ));
+        }
+
+        if rows.len() > TABLE_ROWS {
+            lines.push(format!(
+                "  {ANSI_ITALIC}… {} more, pass `--explain-json <path>` to export all{ANSI_RESET}",
+                rows.len() - TABLE_ROWS
+            ));
+        }
+    }
+
+    lines
+}
+
+fn to_json(events: &[Event]) -> JsonValue {
+    JsonValue::Array(
+        events
+            .iter()
+            .map(|event| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("part".into(), JsonValue::Number(f64::from(event.part)));
+                map.insert("event".into(), JsonValue::String(event.name.into()));
+                map.insert(
+                    "fields".into(),
+                    JsonValue::Object(
+                        event
+                            .fields
+                            .iter()
+                            .map(|(k, v)| ((*k).to_string(), JsonValue::String(v.clone())))
+                            .collect(),
+                    ),
+                );
+                JsonValue::Object(map)
+            })
+            .collect(),
+    )
+}
+
+/// Records a named event with key/value fields if the solution runs with `cargo solve <day> --explain`.
+/// Fields are only evaluated while recording, so the macro costs a single branch otherwise.
+///
+/// ```ignore
+/// advent_of_code::explain!("equation", target = 190, operators = "*");
+/// ```
+#[macro_export]
+macro_rules! explain {
+    ($name:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::template::explain::enabled() {
+            $crate::template::explain::record(
+                $name,
+                vec![$( (stringify!($key), $value.to_string()) ),*],
+            );
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_tables, parse_mode, to_json, Event, Mode};
+
+    fn get_events() -> Vec<Event> {
+        vec![
+            Event {
+                part: 1,
+                name: "equation",
+                fields: vec![("target", "190".into()), ("operators", "*".into())],
+            },
+            Event {
+                part: 1,
+                name: "equation",
+                fields: vec![("target", "3267".into()), ("operators", "+*".into())],
+            },
+            Event {
+                part: 1,
+                name: "unsolvable",
+                fields: vec![("target", "83".into())],
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_mode() {
+        let args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(parse_mode(&args(&["05"])), None);
+        assert_eq!(parse_mode(&args(&["05", "--explain"])), Some(Mode::Table));
+        assert_eq!(
+            parse_mode(&args(&["05", "--explain-json", "out.json"])),
+            Some(Mode::Json("out.json".into()))
+        );
+    }
+
+    #[test]
+    fn formats_tables() {
+        let events = get_events();
+        let lines = format_tables(&events.iter().collect::<Vec<_>>());
+
+        assert_eq!(lines.len(), 7);
+        assert!(lines[0].contains("equation"));
+        assert!(lines[0].contains("(2 events)"));
+        assert!(lines[1].contains("target  operators"));
+        assert_eq!(lines[2], "  190     *");
+        assert_eq!(lines[3], "  3267    +*");
+        assert!(lines[4].contains("(1 event)"));
+        assert_eq!(lines[6], "  83");
+    }
+
+    #[test]
+    fn serializes_json() {
+        let json = to_json(&get_events()).stringify().unwrap();
+        assert!(json.contains(r#""event":"unsolvable""#));
+        assert!(json.contains(r#""target":"3267""#));
+        assert!(json.contains(r#""part":1"#));
+    }
+}
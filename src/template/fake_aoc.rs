@@ -0,0 +1,540 @@
+/// A local stand-in for adventofcode.com, so that downloading, reading and submitting can be tested without the
+/// real site.
+///
+/// [`FakeAoc`] serves the puzzle pages, inputs, answer responses and leaderboards of a scripted [`Scenario`] on
+/// localhost and records every request. aoc-cli can not be pointed at another host, so a [`Project`] puts a
+/// stand-in `aoc` script on the `PATH` that implements the subset of aoc-cli used by the template with `curl`.
+use std::collections::{HashMap, VecDeque};
+use std::ffi::OsStr;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+/// Response of the server to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The previous answer was submitted less than `wait` seconds ago.
+    RateLimited {
+        wait: u32,
+    },
+}
+
+/// The puzzles, inputs, answer responses and leaderboards the server knows about.
+#[derive(Clone, Debug, Default)]
+pub struct Scenario {
+    year: u16,
+    session: Option<String>,
+    puzzles: HashMap<u8, String>,
+    inputs: HashMap<u8, String>,
+    verdicts: HashMap<(u8, u8), VecDeque<Verdict>>,
+    leaderboards: HashMap<String, String>,
+}
+
+impl Scenario {
+    pub fn new(year: u16) -> Self {
+        Self {
+            year,
+            ..Self::default()
+        }
+    }
+
+    /// Requires the session cookie `token` for inputs and answers, like the real site.
+    pub fn session(mut self, token: &str) -> Self {
+        self.session = Some(token.into());
+        self
+    }
+
+    /// Adds the puzzle page of a day. Part two is only shown if it is unlocked, i.e. `part_two` is set.
+    pub fn puzzle(mut self, day: u8, title: &str, part_one: &str, part_two: Option<&str>) -> Self {
+        // one tag per line, so that the stand-in aoc-cli can convert the page with `sed`.
+        let mut page = format!(
+            "<!DOCTYPE html>\n<html>\n<body>\n<main>\n<article class=\"day-desc\">\n<h2>--- Day {day}: {title} ---</h2>\n<p>{part_one}</p>\n</article>\n"
+        );
+        if let Some(part_two) = part_two {
+            page.push_str(&format!(
+                "<article class=\"day-desc\">\n<h2 id=\"part2\">--- Part Two ---</h2>\n<p>{part_two}</p>\n</article>\n"
+            ));
+        }
+        page.push_str("</main>\n</body>\n</html>\n");
+
+        self.puzzles.insert(day, page);
+        self
+    }
+
+    pub fn input(mut self, day: u8, input: &str) -> Self {
+        self.inputs.insert(day, input.into());
+        self
+    }
+
+    /// Queues the responses to the next answers submitted for a part.
+    pub fn answers(
+        mut self,
+        day: u8,
+        part: u8,
+        verdicts: impl IntoIterator<Item = Verdict>,
+    ) -> Self {
+        self.verdicts
+            .entry((day, part))
+            .or_default()
+            .extend(verdicts);
+        self
+    }
+
+    /// Adds the JSON of the private leaderboard `id`.
+    pub fn leaderboard(mut self, id: &str, json: &str) -> Self {
+        self.leaderboards.insert(id.into(), json.into());
+        self
+    }
+}
+
+/// A request received by the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// Value of the `session` cookie.
+    pub session: Option<String>,
+    pub user_agent: Option<String>,
+    pub body: String,
+}
+
+struct State {
+    scenario: Scenario,
+    requests: Vec<Request>,
+}
+
+/// The server, it runs until it is dropped.
+pub struct FakeAoc {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    stopped: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FakeAoc {
+    pub fn start(scenario: Scenario) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("could not bind the fake server");
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(State {
+            scenario,
+            requests: vec![],
+        }));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let thread = thread::spawn({
+            let state = state.clone();
+            let stopped = stopped.clone();
+            move || {
+                for stream in listener.incoming() {
+                    if stopped.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        handle_connection(stream, &state);
+                    }
+                }
+            }
+        });
+
+        Self {
+            addr,
+            state,
+            stopped,
+            thread: Some(thread),
+        }
+    }
+
+    /// Base url of the server, e.g. `http://127.0.0.1:4321`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Returns the requests received so far.
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Sends a GET request to the server, returns the status code and the body.
+    pub fn get(&self, path: &str) -> (u16, String) {
+        let mut stream =
+            TcpStream::connect(self.addr).expect("could not connect to the fake server");
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+            self.addr
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+        let status = head
+            .split(' ')
+            .nth(1)
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default();
+        (status, body.to_string())
+    }
+
+    /// Creates a temporary template project with the given `aoc.toml` that downloads from this server.
+    pub fn project(&self, aoc_toml: &str) -> Project {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.subsec_nanos());
+        let dir = env::temp_dir().join(format!(
+            "aoc-fake-{}-{}-{nanos}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::SeqCst)
+        ));
+
+        let project = Project {
+            dir,
+            url: self.url(),
+        };
+        project.write("aoc.toml", aoc_toml);
+        let year = self.state.lock().unwrap().scenario.year;
+        project.write(
+            ".bin/aoc",
+            &AOC_CLI_STAND_IN
+                .replace("%URL%", &self.url())
+                .replace("%YEAR%", &year.to_string()),
+        );
+        fs::set_permissions(
+            project.path().join(".bin/aoc"),
+            fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+
+        project
+    }
+}
+
+impl Drop for FakeAoc {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // wake up the accept loop.
+        TcpStream::connect(self.addr).ok();
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+/// A temporary directory with an `aoc.toml` and a stand-in aoc-cli, removed on drop.
+pub struct Project {
+    dir: PathBuf,
+    url: String,
+}
+
+impl Project {
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Writes a file relative to the project, creating its directory.
+    pub fn write(&self, path: &str, contents: &str) {
+        let path = self.dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    /// Reads a file relative to the project, `None` if it does not exist.
+    pub fn read(&self, path: &str) -> Option<String> {
+        fs::read_to_string(self.dir.join(path)).ok()
+    }
+
+    /// Returns the `PATH` with the stand-in aoc-cli in front.
+    pub fn path_var(&self) -> String {
+        format!(
+            "{}:{}",
+            self.dir.join(".bin").display(),
+            env::var("PATH").unwrap_or_default()
+        )
+    }
+
+    /// Returns a command that runs `program` in the project with the stand-in aoc-cli, and `AOC_URL` pointing
+    /// curl downloads to the server. The session token is neither inherited from the environment nor read from
+    /// the home directory.
+    pub fn command(&self, program: impl AsRef<OsStr>) -> Command {
+        let mut cmd = Command::new(program);
+        cmd.current_dir(&self.dir)
+            .env("PATH", self.path_var())
+            .env("HOME", &self.dir)
+            .env("AOC_URL", &self.url)
+            .env_remove("ADVENT_OF_CODE_SESSION")
+            .env_remove("AOC_YEAR");
+        cmd
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.dir).ok();
+    }
+}
+
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) {
+    stream.set_read_timeout(Some(Duration::from_secs(5))).ok();
+    let mut reader = BufReader::new(&stream);
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() || request_line.is_empty() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length = headers
+        .get("content-length")
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok();
+
+    let request = Request {
+        method,
+        path,
+        session: headers.get("cookie").and_then(|cookie| {
+            cookie
+                .split(';')
+                .find_map(|x| x.trim().strip_prefix("session="))
+                .map(str::to_string)
+        }),
+        user_agent: headers.get("user-agent").cloned(),
+        body: String::from_utf8_lossy(&body).to_string(),
+    };
+
+    let (status, content_type, body) = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(request.clone());
+        respond(&mut state.scenario, &request)
+    };
+
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .ok();
+}
+
+fn respond(scenario: &mut Scenario, request: &Request) -> (u16, &'static str, String) {
+    const HTML: &str = "text/html";
+    const TEXT: &str = "text/plain";
+    let not_found = (404, TEXT, "404 Not Found\n".to_string());
+
+    let path = request.path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    let logged_in = scenario.session.is_none() || request.session == scenario.session;
+
+    let Some((year, rest)) = segments.split_first() else {
+        return not_found;
+    };
+    if *year != scenario.year.to_string() {
+        return not_found;
+    }
+
+    match (request.method.as_str(), rest) {
+        ("GET", ["day", day]) => match day
+            .parse()
+            .ok()
+            .and_then(|day: u8| scenario.puzzles.get(&day))
+        {
+            Some(page) => (200, HTML, page.clone()),
+            None => not_found,
+        },
+        ("GET", ["day", _, "input"]) | ("POST", ["day", _, "answer"]) if !logged_in => (
+            400,
+            TEXT,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".into(),
+        ),
+        ("GET", ["day", day, "input"]) => match day
+            .parse()
+            .ok()
+            .and_then(|day: u8| scenario.inputs.get(&day))
+        {
+            Some(input) => (200, TEXT, input.clone()),
+            None => not_found,
+        },
+        ("POST", ["day", day, "answer"]) => {
+            let Ok(day) = day.parse::<u8>() else {
+                return not_found;
+            };
+            let level = request
+                .body
+                .split('&')
+                .find_map(|x| x.strip_prefix("level="))
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(1);
+            let verdict = scenario
+                .verdicts
+                .get_mut(&(day, level))
+                .and_then(VecDeque::pop_front);
+            (200, HTML, answer_page(scenario.year, day, verdict))
+        }
+        ("GET", ["leaderboard", "private", "view", file]) => {
+            match file
+                .strip_suffix(".json")
+                .and_then(|id| scenario.leaderboards.get(id))
+            {
+                Some(json) => (200, "application/json", json.clone()),
+                None => not_found,
+            }
+        }
+        _ => not_found,
+    }
+}
+
+/// Renders the response page to an answer, the texts follow the real site.
+fn answer_page(year: u16, day: u8, verdict: Option<Verdict>) -> String {
+    let back = format!("[<a href=\"/{year}/day/{day}\">Return to Day {day}</a>]");
+    let wrong = |hint: &str| {
+        format!("That's not the right answer{hint}.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. {back}")
+    };
+
+    let text = match verdict {
+        Some(Verdict::Correct) => format!("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to finding the Chief Historian. {back}"),
+        Some(Verdict::Wrong) => wrong(""),
+        Some(Verdict::TooHigh) => wrong("; your answer is too high"),
+        Some(Verdict::TooLow) => wrong("; your answer is too low"),
+        Some(Verdict::RateLimited { wait }) => format!("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {wait}s left to wait. {back}"),
+        None => format!("You don't seem to be solving the right level.  Did you already complete it? {back}"),
+    };
+
+    format!("<!DOCTYPE html>\n<html>\n<body>\n<main>\n<article>\n<p>{text}</p>\n</article>\n</main>\n</body>\n</html>\n")
+}
+
+/// Implements `aoc -V`, `aoc download` and `aoc submit` with the arguments passed by `aoc_cli`.
+const AOC_CLI_STAND_IN: &str = r#"#!/bin/sh
+# Stand-in for aoc-cli that talks to a fake Advent of Code server.
+url="%URL%"
+year="%YEAR%"
+day=""
+command=""
+input_file="input"
+puzzle_file="puzzle.md"
+session_file="$HOME/.adventofcode.session"
+puzzle_only=""
+part=""
+answer=""
+
+while [ $# -gt 0 ]; do
+    case "$1" in
+        -V) echo "aoc-cli 0.12.0 (fake)"; exit 0 ;;
+        --year) year="$2"; shift ;;
+        --day) day="$2"; shift ;;
+        --input-file) input_file="$2"; shift ;;
+        --puzzle-file) puzzle_file="$2"; shift ;;
+        --session-file) session_file="$2"; shift ;;
+        --puzzle-only) puzzle_only=1 ;;
+        --overwrite|--description-only) ;;
+        download|submit|read) command="$1" ;;
+        *) if [ -z "$part" ]; then part="$1"; else answer="$1"; fi ;;
+    esac
+    shift
+done
+
+session="$ADVENT_OF_CODE_SESSION"
+if [ -z "$session" ] && [ -f "$session_file" ]; then
+    session=$(cat "$session_file")
+fi
+
+day=$(expr "$day" + 0)
+
+fetch() {
+    curl --silent --show-error --fail -A "aoc-cli-fake" -b "session=$session" "$@"
+}
+
+article() {
+    sed -n '/<article/,/<\/article>/p' | sed -e 's/<h2[^>]*>\(.*\)<\/h2>/\\\1\
+----------/' -e 's/<[^>]*>//g'
+}
+
+case "$command" in
+    download)
+        page=$(fetch "$url/$year/day/$day") || { echo "error: failed to download the puzzle" >&2; exit 1; }
+        mkdir -p "$(dirname "$puzzle_file")"
+        printf '%s\n' "$page" | article > "$puzzle_file"
+        echo "Saved puzzle to '$puzzle_file'" >&2
+        if [ -z "$puzzle_only" ]; then
+            mkdir -p "$(dirname "$input_file")"
+            fetch "$url/$year/day/$day/input" > "$input_file.tmp" || { rm -f "$input_file.tmp"; echo "error: failed to download the input" >&2; exit 1; }
+            mv "$input_file.tmp" "$input_file"
+            echo "Saved input to '$input_file'" >&2
+        fi
+        ;;
+    submit)
+        fetch --data "level=$part&answer=$answer" "$url/$year/day/$day/answer" | article || exit 1
+        ;;
+    *)
+        echo "error: unsupported command '$command'" >&2
+        exit 2
+        ;;
+esac
+"#;
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{FakeAoc, Scenario};
+
+    #[test]
+    fn serves_scenario() {
+        let server = FakeAoc::start(
+            Scenario::new(2024)
+                .session("token")
+                .puzzle(1, "Historian Hysteria", "Part one.", None)
+                .input(1, "3   4\n")
+                .leaderboard("123", "{\"members\":{}}"),
+        );
+
+        let (status, page) = server.get("/2024/day/1");
+        assert_eq!(status, 200);
+        assert!(page.contains("<h2>--- Day 1: Historian Hysteria ---</h2>"));
+        assert!(!page.contains("Part Two"));
+
+        assert_eq!(server.get("/2024/day/1/input").0, 400);
+        assert_eq!(server.get("/2024/day/2").0, 404);
+        assert_eq!(server.get("/2023/day/1").0, 404);
+        assert_eq!(
+            server.get("/2024/leaderboard/private/view/123.json"),
+            (200, "{\"members\":{}}".to_string())
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 5);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2024/day/1");
+        assert_eq!(requests[0].session, None);
+    }
+}
@@ -0,0 +1,200 @@
+/// Content hashes that tell whether a stored benchmark is still representative of a day's code and input.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::config;
+use crate::template::Day;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Computes a hash over the solution file of `day`, the shared library sources and the day's input.
+///
+/// The library sources are all `.rs` files below `./src` except for the solution binaries, `main.rs` and the
+/// template itself, i.e. the helper modules that solutions can import from `advent_of_code`.
+/// Returns `None` if the day has not been scaffolded yet.
+#[must_use]
+pub fn fingerprint(day: Day) -> Option<String> {
+    fingerprint_in(Path::new("."), day)
+}
+
+/// Computes the fingerprint of `day` in the project at `root`.
+fn fingerprint_in(root: &Path, day: Day) -> Option<String> {
+    let bin_path = root.join("src/bin").join(format!("{day}.rs"));
+
+    if !bin_path.exists() {
+        return None;
+    }
+
+    let src = root.join("src");
+    let mut paths = vec![bin_path];
+    collect_library_sources(&src, &src, &mut paths);
+    paths.push(root.join(config::get().paths.input(day)));
+
+    let mut hash = FNV_OFFSET_BASIS;
+
+    for path in &paths {
+        hash = hash_bytes(hash, path.to_string_lossy().as_bytes());
+        // NOTE: a missing input hashes differently from an empty one.
+        match fs::read(path) {
+            Ok(contents) => hash = hash_bytes(hash, &contents),
+            Err(_) => hash = hash_bytes(hash, &[0xff]),
+        }
+    }
+
+    Some(format!("{hash:016x}"))
+}
+
+fn collect_library_sources(src: &Path, dir: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut entries: Vec<PathBuf> = entries.filter_map(|e| Some(e.ok()?.path())).collect();
+    entries.sort();
+
+    for path in entries {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        if path.is_dir() {
+            if dir == src && (name == "bin" || name == "template") {
+                continue;
+            }
+            collect_library_sources(src, &path, paths);
+        } else if name.ends_with(".rs") && !(dir == src && name == "main.rs") {
+            paths.push(path);
+        }
+    }
+}
+
+/// 64-bit FNV-1a, which is stable across platforms and compiler versions unlike `DefaultHasher`.
+fn hash_bytes(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::{env, fs, process};
+
+    use super::{collect_library_sources, fingerprint_in, hash_bytes, FNV_OFFSET_BASIS};
+    use crate::day;
+
+    /// A scaffolded project with two solutions, a shared module and the input of day 1.
+    struct Project(PathBuf);
+
+    impl Project {
+        fn new(name: &str) -> Self {
+            let root = env::temp_dir().join(format!("aoc-fingerprint-{}-{name}", process::id()));
+            let project = Project(root);
+            project.write("src/bin/01.rs", "advent_of_code::solution!(1);");
+            project.write("src/bin/02.rs", "advent_of_code::solution!(2);");
+            project.write("src/main.rs", "fn main() {}");
+            project.write("src/lib.rs", "pub mod grid;\npub mod template;");
+            project.write("src/grid.rs", "pub struct Grid;");
+            project.write("src/template/mod.rs", "pub fn run() {}");
+            project.write("data/inputs/01.txt", "1 2 3\n");
+            project
+        }
+
+        fn write(&self, path: &str, contents: &str) {
+            let path = self.0.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        fn fingerprint(&self) -> String {
+            fingerprint_in(&self.0, day!(1)).unwrap()
+        }
+    }
+
+    impl Drop for Project {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn hashes_known_values() {
+        assert_eq!(hash_bytes(FNV_OFFSET_BASIS, b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash_bytes(FNV_OFFSET_BASIS, b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(
+            hash_bytes(FNV_OFFSET_BASIS, b"foobar"),
+            0x8594_4171_f739_67e8
+        );
+    }
+
+    #[test]
+    fn hashes_incrementally() {
+        let hash = hash_bytes(hash_bytes(FNV_OFFSET_BASIS, b"foo"), b"bar");
+        assert_eq!(hash, hash_bytes(FNV_OFFSET_BASIS, b"foobar"));
+    }
+
+    #[test]
+    fn collects_library_sources() {
+        let project = Project::new("sources");
+        let src = project.0.join("src");
+        project.write("src/helpers/math.rs", "pub fn gcd() {}");
+        project.write("src/helpers/notes.txt", "not a module");
+
+        let mut paths = vec![];
+        collect_library_sources(&src, &src, &mut paths);
+
+        let relative: Vec<&Path> = paths
+            .iter()
+            .map(|x| x.strip_prefix(&project.0).unwrap())
+            .collect();
+        assert_eq!(
+            relative,
+            [
+                Path::new("src/grid.rs"),
+                Path::new("src/helpers/math.rs"),
+                Path::new("src/lib.rs")
+            ]
+        );
+    }
+
+    #[test]
+    fn changes_with_shared_code_and_input() {
+        let project = Project::new("changes");
+        let initial = project.fingerprint();
+        assert_eq!(project.fingerprint(), initial);
+
+        // other solutions, the binary entry point and the template are not part of the fingerprint.
+        project.write("src/bin/02.rs", "advent_of_code::solution!(2, 1);");
+        project.write("src/main.rs", "fn main() { println!(); }");
+        project.write("src/template/mod.rs", "pub fn run() { run() }");
+        assert_eq!(project.fingerprint(), initial);
+
+        project.write("src/grid.rs", "pub struct Grid(Vec<u8>);");
+        let shared = project.fingerprint();
+        assert_ne!(shared, initial);
+
+        project.write("data/inputs/01.txt", "1 2 4\n");
+        let input = project.fingerprint();
+        assert_ne!(input, shared);
+
+        project.write("src/bin/01.rs", "advent_of_code::solution!(1, 2);");
+        assert_ne!(project.fingerprint(), input);
+    }
+
+    #[test]
+    fn hashes_missing_inputs() {
+        let project = Project::new("missing");
+        fs::remove_file(project.0.join("data/inputs/01.txt")).unwrap();
+        let missing = project.fingerprint();
+        assert_eq!(project.fingerprint(), missing);
+
+        project.write("data/inputs/01.txt", "");
+        assert_ne!(project.fingerprint(), missing);
+
+        // days that are not scaffolded have no fingerprint.
+        assert_eq!(fingerprint_in(&project.0, day!(3)), None);
+    }
+}
@@ -0,0 +1,363 @@
+/// The common shapes of puzzle inputs. `cargo scaffold` detects the shape of a downloaded input and
+/// generates a matching parse function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    /// A rectangle of characters, e.g. a map.
+    Grid { width: usize, height: usize },
+    /// A rectangle of single digits, e.g. a height map.
+    DigitGrid { width: usize, height: usize },
+    /// One integer per line.
+    Numbers { lines: usize },
+    /// Several integers per line, separated by spaces, commas or other delimiters.
+    NumberRows { lines: usize },
+    /// `key: values` lines whose key and values are integers.
+    NumberRecords { lines: usize },
+    /// `key: value` lines.
+    Records { lines: usize },
+    /// Two blocks of lines, separated by a blank line.
+    TwoSections,
+    /// More than two blocks of lines, separated by blank lines.
+    Sections { sections: usize },
+    /// A single line of digits.
+    Digits { len: usize },
+    /// A single line of integers.
+    NumberList { len: usize },
+    /// A single line of text.
+    Line,
+    /// Lines without a common structure.
+    Lines { lines: usize },
+}
+
+impl Shape {
+    /// Returns the shape of `input`, or `None` if it is empty.
+    pub fn detect(input: &str) -> Option<Self> {
+        let input = input.trim_end();
+        if input.trim().is_empty() {
+            return None;
+        }
+
+        let lines: Vec<&str> = input.lines().collect();
+
+        if let [line] = lines[..] {
+            let numbers = integers(line);
+            return Some(if line.chars().all(|c| c.is_ascii_digit()) {
+                Shape::Digits { len: line.len() }
+            } else if numbers.len() > 1 && is_number_row(line) {
+                Shape::NumberList { len: numbers.len() }
+            } else {
+                Shape::Line
+            });
+        }
+
+        if lines.iter().any(|line| line.trim().is_empty()) {
+            let sections = input.split("\n\n").count();
+            return Some(if sections == 2 {
+                Shape::TwoSections
+            } else {
+                Shape::Sections { sections }
+            });
+        }
+
+        if lines.iter().all(|line| line.contains(": ")) {
+            let numeric = lines.iter().all(|line| {
+                line.split_once(": ").is_some_and(|(key, values)| {
+                    key.parse::<i64>().is_ok() && is_number_row(values)
+                })
+            });
+            return Some(if numeric {
+                Shape::NumberRecords { lines: lines.len() }
+            } else {
+                Shape::Records { lines: lines.len() }
+            });
+        }
+
+        let width = lines[0].len();
+        let is_rectangle = width > 1
+            && lines
+                .iter()
+                .all(|line| line.len() == width && !line.contains(char::is_whitespace));
+
+        if is_rectangle {
+            let height = lines.len();
+            let digits = lines.iter().all(|l| l.chars().all(|c| c.is_ascii_digit()));
+
+            // short lines of digits are more likely numbers than a grid.
+            if !digits {
+                return Some(Shape::Grid { width, height });
+            } else if width >= 5 {
+                return Some(Shape::DigitGrid { width, height });
+            }
+        }
+
+        if lines.iter().all(|line| line.trim().parse::<i64>().is_ok()) {
+            return Some(Shape::Numbers { lines: lines.len() });
+        }
+
+        if lines.iter().all(|line| is_number_row(line)) {
+            return Some(Shape::NumberRows { lines: lines.len() });
+        }
+
+        Some(Shape::Lines { lines: lines.len() })
+    }
+
+    pub fn describe(self) -> String {
+        match self {
+            Shape::Grid { width, height } => format!("grid of characters ({width}x{height})"),
+            Shape::DigitGrid { width, height } => format!("grid of digits ({width}x{height})"),
+            Shape::Numbers { lines } => format!("{lines} lines with one integer each"),
+            Shape::NumberRows { lines } => format!("{lines} lines of integers"),
+            Shape::NumberRecords { lines } => format!("{lines} `key: values` records of integers"),
+            Shape::Records { lines } => format!("{lines} `key: value` records"),
+            Shape::TwoSections => "two blank-line-separated sections".into(),
+            Shape::Sections { sections } => format!("{sections} blank-line-separated sections"),
+            Shape::Digits { len } => format!("a single line of {len} digits"),
+            Shape::NumberList { len } => format!("a single line of {len} integers"),
+            Shape::Line => "a single line of text".into(),
+            Shape::Lines { lines } => format!("{lines} lines of text"),
+        }
+    }
+
+    /// Answer type that fits the shape, used if neither `--answer-type` nor `aoc.toml` set one.
+    pub fn answer_type(self) -> Option<&'static str> {
+        match self {
+            Shape::Numbers { .. }
+            | Shape::NumberRows { .. }
+            | Shape::NumberRecords { .. }
+            | Shape::NumberList { .. }
+            | Shape::Digits { .. } => Some("u64"),
+            _ => None,
+        }
+    }
+
+    /// Returns the name the parsed input is bound to in the parts.
+    fn binding(self) -> &'static str {
+        match self {
+            Shape::Grid { .. } | Shape::DigitGrid { .. } => "grid",
+            Shape::Numbers { .. } | Shape::NumberList { .. } => "numbers",
+            Shape::NumberRows { .. } => "rows",
+            Shape::NumberRecords { .. } | Shape::Records { .. } => "records",
+            Shape::TwoSections => "(first, second)",
+            Shape::Sections { .. } => "sections",
+            Shape::Digits { .. } => "digits",
+            Shape::Line => "line",
+            Shape::Lines { .. } => "lines",
+        }
+    }
+
+    /// Returns the source of a `parse` function for the shape.
+    fn parse_fn(self) -> &'static str {
+        match self {
+            Shape::Grid { .. } => {
+                "fn parse(input: &str) -> Vec<Vec<u8>> {
+    input.lines().map(|line| line.bytes().collect()).collect()
+}"
+            }
+            Shape::DigitGrid { .. } => {
+                "fn parse(input: &str) -> Vec<Vec<u8>> {
+    input
+        .lines()
+        .map(|line| line.bytes().map(|b| b - b'0').collect())
+        .collect()
+}"
+            }
+            Shape::Numbers { .. } => {
+                "fn parse(input: &str) -> Vec<i64> {
+    input
+        .lines()
+        .map(|line| line.trim().parse().unwrap())
+        .collect()
+}"
+            }
+            Shape::NumberRows { .. } => {
+                "fn parse(input: &str) -> Vec<Vec<i64>> {
+    input.lines().map(integers).collect()
+}
+
+fn integers(s: &str) -> Vec<i64> {
+    s.split(|c: char| !c.is_ascii_digit() && c != '-')
+        .filter_map(|x| x.parse().ok())
+        .collect()
+}"
+            }
+            Shape::NumberRecords { .. } => {
+                "fn parse(input: &str) -> Vec<(i64, Vec<i64>)> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let (key, values) = line.split_once(\": \")?;
+            Some((key.parse().ok()?, integers(values)))
+        })
+        .collect()
+}
+
+fn integers(s: &str) -> Vec<i64> {
+    s.split(|c: char| !c.is_ascii_digit() && c != '-')
+        .filter_map(|x| x.parse().ok())
+        .collect()
+}"
+            }
+            Shape::Records { .. } => {
+                "fn parse(input: &str) -> Vec<(&str, &str)> {
+    input
+        .lines()
+        .filter_map(|line| line.split_once(\": \"))
+        .collect()
+}"
+            }
+            Shape::TwoSections => {
+                "fn parse(input: &str) -> (Vec<&str>, Vec<&str>) {
+    let (first, second) = input.split_once(\"\\n\\n\").unwrap();
+    (first.lines().collect(), second.lines().collect())
+}"
+            }
+            Shape::Sections { .. } => {
+                "fn parse(input: &str) -> Vec<Vec<&str>> {
+    input
+        .split(\"\\n\\n\")
+        .map(|section| section.lines().collect())
+        .collect()
+}"
+            }
+            Shape::Digits { .. } => {
+                "fn parse(input: &str) -> Vec<u8> {
+    input.trim().bytes().map(|b| b - b'0').collect()
+}"
+            }
+            Shape::NumberList { .. } => {
+                "fn parse(input: &str) -> Vec<i64> {
+    input
+        .split(|c: char| !c.is_ascii_digit() && c != '-')
+        .filter_map(|x| x.parse().ok())
+        .collect()
+}"
+            }
+            Shape::Line => {
+                "fn parse(input: &str) -> &str {
+    input.trim()
+}"
+            }
+            Shape::Lines { .. } => {
+                "fn parse(input: &str) -> Vec<&str> {
+    input.lines().collect()
+}"
+            }
+        }
+    }
+
+    /// Generates a solution template for the shape. The tests are taken from `base`, the default template.
+    pub fn template(self, base: &str) -> String {
+        let tests = base.find("#[cfg(test)]").map_or("", |index| &base[index..]);
+
+        let part = |name: &str| {
+            format!(
+                "pub fn {name}(input: &str) -> Option<%ANSWER_TYPE%> {{\n    let {} = parse(input);\n    None\n}}\n",
+                self.binding()
+            )
+        };
+
+        format!(
+            "advent_of_code::solution!(%DAY_NUMBER%, parse = parse);\n\n// input: {}\n{}\n\n{}\n{}\n{tests}",
+            self.describe(),
+            self.parse_fn(),
+            part("part_one"),
+            part("part_two"),
+        )
+    }
+}
+
+fn integers(s: &str) -> Vec<i64> {
+    s.split(|c: char| !c.is_ascii_digit() && c != '-')
+        .filter_map(|x| x.parse().ok())
+        .collect()
+}
+
+/// Whether `s` only consists of integers and delimiters.
+fn is_number_row(s: &str) -> bool {
+    !integers(s).is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '-' | ',' | '|' | ' ' | '\t' | ';'))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Shape;
+
+    #[test]
+    fn detects_grids() {
+        assert_eq!(
+            Shape::detect("MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\n"),
+            Some(Shape::Grid {
+                width: 10,
+                height: 3
+            })
+        );
+        assert_eq!(
+            Shape::detect("89010123\n78121874\n"),
+            Some(Shape::DigitGrid {
+                width: 8,
+                height: 2
+            })
+        );
+    }
+
+    #[test]
+    fn detects_numbers() {
+        assert_eq!(
+            Shape::detect("199\n200\n208\n"),
+            Some(Shape::Numbers { lines: 3 })
+        );
+        assert_eq!(
+            Shape::detect("3   4\n4   3\n2   5\n"),
+            Some(Shape::NumberRows { lines: 3 })
+        );
+        assert_eq!(
+            Shape::detect("190: 10 19\n3267: 81 40 27\n"),
+            Some(Shape::NumberRecords { lines: 2 })
+        );
+        assert_eq!(
+            Shape::detect("2333133121414131402\n"),
+            Some(Shape::Digits { len: 19 })
+        );
+        assert_eq!(
+            Shape::detect("125 17\n"),
+            Some(Shape::NumberList { len: 2 })
+        );
+    }
+
+    #[test]
+    fn detects_text() {
+        assert_eq!(
+            Shape::detect("47|53\n97|13\n\n75,47,61\n97,61,53\n"),
+            Some(Shape::TwoSections)
+        );
+        assert_eq!(
+            Shape::detect("a\n\nb\n\nc"),
+            Some(Shape::Sections { sections: 3 })
+        );
+        assert_eq!(
+            Shape::detect("Button A: X+94, Y+34\nPrize: X=8400, Y=5400\n"),
+            Some(Shape::Records { lines: 2 })
+        );
+        assert_eq!(
+            Shape::detect("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)\n"),
+            Some(Shape::Line)
+        );
+        assert_eq!(
+            Shape::detect("p=0,4 v=3,-3\np=6,3 v=-1,-3\n"),
+            Some(Shape::Lines { lines: 2 })
+        );
+        assert_eq!(Shape::detect("\n"), None);
+    }
+
+    #[test]
+    fn generates_template() {
+        let base = "advent_of_code::solution!(%DAY_NUMBER%);\n\n#[cfg(test)]\nmod tests {}\n";
+        let template = Shape::TwoSections.template(base);
+
+        assert!(template.starts_with("advent_of_code::solution!(%DAY_NUMBER%, parse = parse);"));
+        assert!(template.contains("// input: two blank-line-separated sections"));
+        assert!(template.contains("fn parse(input: &str) -> (Vec<&str>, Vec<&str>)"));
+        assert!(template.contains("    let (first, second) = parse(input);"));
+        assert!(template.ends_with("#[cfg(test)]\nmod tests {}\n"));
+    }
+}
@@ -0,0 +1,94 @@
+/// Verbosity-gated debug output for solutions, see the `debug!` and `trace!` macros.
+use std::env;
+use std::fmt::Arguments;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use crate::template::{Day, ANSI_ITALIC, ANSI_RESET};
+
+static VERBOSITY: OnceLock<u8> = OnceLock::new();
+
+// NOTE: atomics instead of thread locals, solutions might log from rayon worker threads.
+static DAY: AtomicU8 = AtomicU8::new(0);
+static PART: AtomicU8 = AtomicU8::new(0);
+
+/// Sets the day and part that subsequent log lines are tagged with.
+pub fn set_context(day: Day, part: u8) {
+    DAY.store(day.into_inner(), Ordering::Relaxed);
+    PART.store(part, Ordering::Relaxed);
+}
+
+/// Whether messages of `level` (1 for `debug!`, 2 for `trace!`) are printed.
+pub fn enabled(level: u8) -> bool {
+    *VERBOSITY.get_or_init(|| parse_verbosity(env::args())) >= level
+}
+
+#[doc(hidden)]
+pub fn write(label: &str, args: Arguments) {
+    let day = DAY.load(Ordering::Relaxed);
+    let part = PART.load(Ordering::Relaxed);
+    eprintln!("{ANSI_ITALIC}[day {day:02} part {part} {label}]{ANSI_RESET} {args}");
+}
+
+/// Parses the `-v` (debug) and `-vv` (trace) flags.
+fn parse_verbosity(args: impl Iterator<Item = String>) -> u8 {
+    args.map(|arg| match arg.as_str() {
+        "-v" => 1,
+        "-vv" => 2,
+        _ => 0,
+    })
+    .max()
+    .unwrap_or(0)
+}
+
+/// Prints a message to stderr if `cargo solve` was called with `-v` or `-vv`.
+/// Output is tagged with the day and part, and compiled out of release builds and therefore benchmarks.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::__log!(1, "debug", $($arg)*)
+    };
+}
+
+/// Prints a message to stderr if `cargo solve` was called with `-vv`.
+/// Output is tagged with the day and part, and compiled out of release builds and therefore benchmarks.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::__log!(2, "trace", $($arg)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log {
+    ($level:expr, $label:expr, $($arg:tt)*) => {
+        // NOTE: `cfg!` instead of `#[cfg]` keeps the arguments type-checked and used in release builds,
+        // the branch is still removed by the compiler.
+        if cfg!(debug_assertions) && $crate::template::log::enabled($level) {
+            $crate::template::log::write($label, format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_verbosity;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn parses_verbosity() {
+        assert_eq!(parse_verbosity(args(&["target/debug/01"])), 0);
+        assert_eq!(parse_verbosity(args(&["target/debug/01", "-v"])), 1);
+        assert_eq!(
+            parse_verbosity(args(&["target/debug/01", "-vv", "--part", "1"])),
+            2
+        );
+    }
+}
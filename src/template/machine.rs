@@ -0,0 +1,180 @@
+/// Describes the host and toolchain that produced a set of timings.
+use std::{collections::HashMap, env, fmt::Display, fs, process::Command, thread};
+use tinyjson::JsonValue;
+
+/// Host and toolchain metadata. Timings are only comparable if they were measured on equal machines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Machine {
+    pub cpu: String,
+    pub cores: usize,
+    pub rustc: String,
+    pub profile: String,
+    pub threads: usize,
+}
+
+impl Machine {
+    /// Detects the metadata of the current host. `threads` is the size of the rayon pool, if configured.
+    pub fn detect(is_release: bool, threads: Option<usize>) -> Self {
+        // NOTE: `available_parallelism` respects the CPU affinity of the process, which is what rayon uses.
+        let available = thread::available_parallelism().map_or(1, std::num::NonZero::get);
+
+        Self {
+            cpu: detect_cpu().unwrap_or_else(|| "unknown CPU".into()),
+            cores: detect_cores().unwrap_or(available),
+            rustc: detect_rustc().unwrap_or_else(|| "unknown rustc".into()),
+            profile: if is_release { "release" } else { "debug" }.into(),
+            threads: threads.or_else(detect_threads).unwrap_or(available),
+        }
+    }
+}
+
+impl Display for Machine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} cores, {} threads), {}, {} profile",
+            self.cpu, self.cores, self.threads, self.rustc, self.profile
+        )
+    }
+}
+
+fn detect_cpu() -> Option<String> {
+    // linux exposes the model in `/proc/cpuinfo`, windows in the environment.
+    fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| {
+            cpuinfo
+                .lines()
+                .find(|l| l.starts_with("model name"))
+                .and_then(|l| l.split(':').nth(1))
+                .map(|s| s.trim().to_string())
+        })
+        .or_else(|| env::var("PROCESSOR_IDENTIFIER").ok())
+        .or_else(|| {
+            let output = Command::new("sysctl")
+                .args(["-n", "machdep.cpu.brand_string"])
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        })
+        .filter(|s| !s.is_empty())
+}
+
+/// Counts all cores of the host, regardless of the process being pinned to a subset of them.
+fn detect_cores() -> Option<usize> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    let cores = cpuinfo
+        .lines()
+        .filter(|l| l.starts_with("processor"))
+        .count();
+    (cores > 0).then_some(cores)
+}
+
+fn detect_rustc() -> Option<String> {
+    let output = Command::new("rustc").arg("-V").output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Mirrors how rayon sizes its global pool in the solution binaries if `--threads` is not passed.
+fn detect_threads() -> Option<usize> {
+    env::var("RAYON_NUM_THREADS")
+        .ok()?
+        .parse()
+        .ok()
+        .filter(|x| *x > 0)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Machine> for JsonValue {
+    fn from(value: &Machine) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+            map.insert("threads".into(), JsonValue::Number(value.threads as f64));
+        }
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Machine {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected machine to be a JSON object.")?;
+
+        let get_string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected machine.{key} to be a string."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as usize)
+                .ok_or(format!("Expected machine.{key} to be a number."))
+        };
+
+        Ok(Machine {
+            cpu: get_string("cpu")?,
+            cores: get_number("cores")?,
+            rustc: get_string("rustc")?,
+            profile: get_string("profile")?,
+            threads: get_number("threads")?,
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Machine;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_json() {
+        let machine = Machine {
+            cpu: "AMD Ryzen 9 7950X 16-Core Processor".into(),
+            cores: 32,
+            rustc: "rustc 1.83.0 (90b35a623 2024-11-26)".into(),
+            profile: "release".into(),
+            threads: 32,
+        };
+
+        let json = JsonValue::from(&machine);
+        assert_eq!(Machine::try_from(&json).unwrap(), machine);
+    }
+
+    #[test]
+    fn displays_summary() {
+        let machine = Machine {
+            cpu: "Apple M1".into(),
+            cores: 8,
+            rustc: "rustc 1.83.0".into(),
+            profile: "release".into(),
+            threads: 4,
+        };
+
+        assert_eq!(
+            machine.to_string(),
+            "Apple M1 (8 cores, 4 threads), rustc 1.83.0, release profile"
+        );
+    }
+}
@@ -0,0 +1,452 @@
+/// Renders the Markdown puzzle descriptions written by aoc-cli for the terminal, and converts puzzle pages to them.
+use crate::template::terminal::sanitize;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const ANSI_CODE: &str = "\x1b[36m";
+const ANSI_DIM: &str = "\x1b[2m";
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+
+/// Base of the relative links in puzzle descriptions, e.g. `/2024/day/5/input`.
+const AOC_URL: &str = "https://adventofcode.com";
+
+/// Returns the section of a puzzle description that belongs to `part`.
+/// Part one ends and part two starts at the `--- Part Two ---` heading.
+pub fn part_section(puzzle: &str, part: u8) -> Option<&str> {
+    let start = puzzle
+        .match_indices("--- Part Two ---")
+        .next()
+        .map(|(index, _)| puzzle[..index].rfind('\n').map_or(0, |x| x + 1));
+
+    match (part, start) {
+        (1, Some(start)) => Some(&puzzle[..start]),
+        (1, None) => Some(puzzle),
+        (2, start) => start.map(|start| &puzzle[start..]),
+        _ => None,
+    }
+}
+
+/// Renders headings, emphasis, code and links with ANSI escape codes, wrapping paragraphs at `width` columns.
+pub fn render(markdown: &str, width: usize) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut out = vec![];
+    let mut in_fence = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let next = lines.get(i + 1).copied().unwrap_or_default();
+        i += 1;
+
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+
+        if in_fence || line.starts_with("    ") {
+            let code = line
+                .strip_prefix("    ")
+                .filter(|_| !in_fence)
+                .unwrap_or(line);
+            out.push(format!("    {ANSI_CODE}{code}{ANSI_RESET}"));
+            continue;
+        }
+
+        // aoc-cli underlines headings, e.g. `\--- Day 5: Print Queue ---` followed by `----------`.
+        if !line.trim().is_empty() && is_underline(next) {
+            i += 1;
+            out.push(format!("{ANSI_BOLD}{}{ANSI_RESET}", inline(line)));
+            continue;
+        }
+
+        if let Some(heading) = line.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim();
+            out.push(format!("{ANSI_BOLD}{}{ANSI_RESET}", inline(heading)));
+        } else if let Some(item) = line.strip_prefix("* ").or(line.strip_prefix("- ")) {
+            out.extend(wrap(&format!("  • {}", inline(item)), width, "    "));
+        } else if let Some(quote) = line.strip_prefix("> ") {
+            out.extend(wrap(
+                &format!("{ANSI_ITALIC}{}{ANSI_RESET}", inline(quote)),
+                width,
+                "",
+            ));
+        } else {
+            out.extend(wrap(&inline(line), width, ""));
+        }
+    }
+
+    out.join("\n")
+}
+
+/// Converts the `<article>` elements of a puzzle page to Markdown in the format that aoc-cli writes.
+/// Returns `None` if the page has no puzzle description, e.g. because the puzzle is not unlocked yet.
+pub fn from_html(page: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut pre = String::new();
+    let mut links: Vec<String> = vec![];
+    let (mut in_article, mut in_pre, mut found) = (false, false, false);
+    // text is only kept inside of blocks, the whitespace between tags is not part of the description.
+    let mut blocks = 0_usize;
+    let mut rest = page;
+
+    while let Some(start) = rest.find('<') {
+        let text = decode_entities(&rest[..start]);
+        let end = start + rest[start..].find('>')?;
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        if in_pre {
+            pre.push_str(&text);
+        } else if in_article && blocks > 0 {
+            push_text(&mut out, &text);
+        }
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        if name == "article" {
+            in_article = !closing;
+            found = true;
+            continue;
+        }
+
+        if !in_article {
+            continue;
+        }
+
+        match (name.as_str(), closing) {
+            ("pre", false) => in_pre = true,
+            ("pre", true) => {
+                in_pre = false;
+                for line in std::mem::take(&mut pre).trim_end_matches('\n').lines() {
+                    out.push_str(&format!("    {line}\n"));
+                }
+                out.push('\n');
+            }
+            _ if in_pre => {}
+            ("h2", false) => {
+                blocks += 1;
+                out.push('\\');
+            }
+            ("h2", true) => {
+                blocks = blocks.saturating_sub(1);
+                out.push_str("\n----------\n\n");
+            }
+            ("p", false) => blocks += 1,
+            ("p", true) => {
+                blocks = blocks.saturating_sub(1);
+                out.push_str("\n\n");
+            }
+            ("li", false) => {
+                blocks += 1;
+                out.push_str("* ");
+            }
+            ("li", true) => {
+                blocks = blocks.saturating_sub(1);
+                out.push('\n');
+            }
+            ("ul", true) => out.push('\n'),
+            ("code", _) => out.push('`'),
+            ("em", _) => out.push('*'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => out.push_str(&format!("]({})", links.pop().unwrap_or_default())),
+            _ => {}
+        }
+    }
+
+    found.then(|| {
+        let lines: Vec<&str> = out.lines().map(str::trim_end).collect();
+        format!("{}\n", lines.join("\n").trim_end())
+    })
+}
+
+/// Appends text of a block with collapsed whitespace, escaping the characters that are Markdown syntax.
+fn push_text(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !out.ends_with([' ', '\n', '\\']) && !out.ends_with("* ") {
+                out.push(' ');
+            }
+            continue;
+        }
+
+        if matches!(c, '\\' | '*' | '`' | '[' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+/// Returns the value of a quoted attribute of a tag, e.g. `href` of `a href="/2024/day/5/input"`.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = start + tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let decoded = entity.and_then(|(name, _)| match name {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => name
+                .strip_prefix("#x")
+                .and_then(|x| u32::from_str_radix(x, 16).ok())
+                .or_else(|| name.strip_prefix('#')?.parse().ok())
+                .and_then(char::from_u32),
+        });
+
+        match (decoded, entity) {
+            (Some(c), Some((_, end))) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn is_underline(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='))
+}
+
+/// Renders the inline markup of a line: escapes, `code`, `*emphasis*` and `[links](url)`.
+fn inline(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::new();
+    let mut code = false;
+    let mut emphasis = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            '\\' if next.is_some_and(|x| x.is_ascii_punctuation()) => {
+                out.push(next.unwrap_or_default());
+                i += 2;
+                continue;
+            }
+            '`' => code = !code,
+            '*' if emphasis || next.is_some_and(|x| !x.is_whitespace()) => {
+                // `**strong**` is rendered like `*emphasis*`.
+                if next == Some('*') {
+                    i += 1;
+                }
+                emphasis = !emphasis;
+            }
+            '[' if !code => {
+                if let Some((text, url, len)) = link(&chars[i..]) {
+                    let url = if url.starts_with('/') {
+                        format!("{AOC_URL}{url}")
+                    } else {
+                        url
+                    };
+                    out.push_str(&format!(
+                        "{ANSI_UNDERLINE}{}{ANSI_RESET} {ANSI_DIM}({url}){ANSI_RESET}",
+                        inline(&text)
+                    ));
+                    out.push_str(&style(code, emphasis));
+                    i += len;
+                } else {
+                    out.push(c);
+                    i += 1;
+                }
+                continue;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+                continue;
+            }
+        }
+
+        // a style changed: reset and apply the active ones.
+        out.push_str(ANSI_RESET);
+        out.push_str(&style(code, emphasis));
+        i += 1;
+    }
+
+    if code || emphasis {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+fn style(code: bool, emphasis: bool) -> String {
+    let mut style = String::new();
+    if code {
+        style.push_str(ANSI_CODE);
+    }
+    if emphasis {
+        style.push_str(ANSI_BOLD);
+    }
+    style
+}
+
+/// Parses `[text](url)` at the start of `chars`, returns the text, the url and the length of the link.
+fn link(chars: &[char]) -> Option<(String, String, usize)> {
+    let close = chars.iter().position(|&c| c == ']')?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = close + 2 + chars[close + 2..].iter().position(|&c| c == ')')?;
+
+    Some((
+        chars[1..close].iter().collect(),
+        chars[close + 2..end].iter().collect(),
+        end + 1,
+    ))
+}
+
+/// Wraps a rendered line at `width` visible columns, continuation lines start with `indent`.
+fn wrap(line: &str, width: usize, indent: &str) -> Vec<String> {
+    let content = line.trim_start_matches(' ');
+    let mut lines = vec![];
+    let mut current = line[..line.len() - content.len()].to_string();
+    let mut current_width = current.len();
+
+    for (i, word) in content.split(' ').enumerate() {
+        let word_width = sanitize(word).chars().count();
+        if i > 0 && current_width + 1 + word_width > width {
+            lines.push(std::mem::take(&mut current));
+            current.push_str(indent);
+            current_width = indent.len();
+        } else if i > 0 {
+            current.push(' ');
+            current_width += 1;
+        }
+        current.push_str(word);
+        current_width += word_width;
+    }
+
+    lines.push(current);
+    lines
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_html, inline, part_section, render, wrap};
+    use crate::template::terminal::sanitize;
+
+    const PUZZLE: &str = "\\--- Day 5: Print Queue ---
+----------
+
+Of the `*6*` updates, see [the input](/2024/day/5/input).
+
+    75,47,61
+
+\\--- Part Two ---
+----------
+
+After ordering, the sum is `*123*`.
+";
+
+    #[test]
+    fn splits_parts() {
+        assert!(part_section(PUZZLE, 1).unwrap().ends_with("75,47,61\n\n"));
+        assert!(part_section(PUZZLE, 2)
+            .unwrap()
+            .starts_with("\\--- Part Two ---"));
+        assert_eq!(part_section("\\--- Day 5: Print Queue ---", 2), None);
+    }
+
+    #[test]
+    fn renders_inline_markup() {
+        assert_eq!(inline("a \\* b"), "a * b");
+        assert_eq!(inline("a [b"), "a [b");
+        assert_eq!(sanitize(&inline("`*6*`")), "6");
+        assert!(inline("`*6*`").contains("\x1b[36m\x1b[1m6"));
+        assert_eq!(
+            inline("[the input](/2024/day/5/input)"),
+            "\x1b[4mthe input\x1b[0m \x1b[2m(https://adventofcode.com/2024/day/5/input)\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn renders_blocks() {
+        let rendered = render(PUZZLE, 80);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines[0], "\x1b[1m--- Day 5: Print Queue ---\x1b[0m");
+        assert_eq!(lines[4], "    \x1b[36m75,47,61\x1b[0m");
+        assert_eq!(lines[6], "\x1b[1m--- Part Two ---\x1b[0m");
+    }
+
+    #[test]
+    fn converts_puzzle_pages() {
+        let page = r#"<!DOCTYPE html>
+<html><body><main>
+<article class="day-desc"><h2>--- Day 5: Print Queue ---</h2><p>Of the <code><em>6</em></code> updates,
+ see <a href="/2024/day/5/input" target="_blank">the input</a> &amp; the <em>rules</em>:</p>
+<pre><code>47|53
+97|13
+</code></pre>
+<ul>
+<li>Pages <code>a*b</code> &lt;3.</li>
+<li>Done.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>143</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>After ordering, the sum is <code><em>123</em></code>.</p></article>
+</main></body></html>
+"#;
+
+        assert_eq!(
+            from_html(page).unwrap(),
+            "\\--- Day 5: Print Queue ---
+----------
+
+Of the `*6*` updates, see [the input](/2024/day/5/input) & the *rules*:
+
+    47|53
+    97|13
+
+* Pages `a\\*b` <3.
+* Done.
+
+\\--- Part Two ---
+----------
+
+After ordering, the sum is `*123*`.
+"
+        );
+        assert_eq!(from_html("<html><p>Not found.</p></html>"), None);
+    }
+
+    #[test]
+    fn wraps_lines() {
+        assert_eq!(
+            wrap("aaa \x1b[1mbbb\x1b[0m ccc", 7, "  "),
+            vec!["aaa \x1b[1mbbb\x1b[0m", "  ccc"]
+        );
+        assert_eq!(wrap("  • a b", 5, "    "), vec!["  • a", "    b"]);
+    }
+}
@@ -1,25 +1,49 @@
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
+pub mod error;
+pub mod examples;
+pub mod explain;
+#[cfg(all(unix, feature = "test_lib"))]
+pub mod fake_aoc;
+pub mod log;
 pub mod runner;
+pub mod spans;
 
 pub use day::*;
 
+mod capture;
 mod day;
+pub mod day_expr;
+mod fingerprint;
+mod input_shape;
+mod machine;
+mod markdown;
+pub mod noise;
 mod readme_benchmarks;
 mod run_multi;
+mod templates;
+mod terminal;
 mod timings;
+mod watch;
+mod worktree;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_CLEAR_LINE: &str = "\x1b[2K";
 
 /// Helper function that reads a text file to a string.
+/// `folder` names a data folder like `inputs` or `examples`, its location is configured in `aoc.toml`.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(config::get().paths.folder(folder))
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -29,8 +53,7 @@ pub fn read_file(folder: &str, day: Day) -> String {
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
+        .join(config::get().paths.folder(folder))
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
@@ -39,19 +62,37 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Within the enabled parts, `cargo solve <day> --part <n>` selects a part at runtime.
+///
+/// Instead of the part number, named variants of a part can be passed, e.g.
+/// `solution!(7, part_one = [naive, fast], part_two = [part_two])`. All variants of a part are run
+/// and compared: the runner fails if they disagree on the answer and prints their timings side by side.
+/// The last variant of a part is the primary one, its answer is submitted and its timing is stored.
+///
+/// If the parts share a parse function, it can be named right after the day, e.g. `solution!(5, parse = parse)`.
+/// It is then run on its own before the parts and `cargo time` reports its timing in the `parse` column.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, parse = $parse:ident)?) => {
+        $crate::solution!(@impl $day, [$($parse)?], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr $(, parse = $parse:ident)?, 1) => {
+        $crate::solution!(@impl $day, [$($parse)?], [part_one, 1]);
+    };
+    ($day:expr $(, parse = $parse:ident)?, 2) => {
+        $crate::solution!(@impl $day, [$($parse)?], [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr $(, parse = $parse:ident)?, part_one = [$($one:ident),+ $(,)?], part_two = [$($two:ident),+ $(,)?] $(,)?) => {
+        $crate::solution!(@variants $day, [$($parse)?], [1, [$($one),+]] [2, [$($two),+]]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr $(, parse = $parse:ident)?, part_one = [$($one:ident),+ $(,)?] $(,)?) => {
+        $crate::solution!(@variants $day, [$($parse)?], [1, [$($one),+]]);
+    };
+    ($day:expr $(, parse = $parse:ident)?, part_two = [$($two:ident),+ $(,)?] $(,)?) => {
+        $crate::solution!(@variants $day, [$($parse)?], [2, [$($two),+]]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@header $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +100,55 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(not(feature = "dhat-heap"))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::PeakAlloc = $crate::template::alloc::PeakAlloc;
+    };
+
+    (@setup) => {
+        #[cfg(not(feature = "dhat-heap"))]
+        $crate::template::alloc::install();
+        $crate::template::runner::configure_threads();
+    };
+
+    (@impl $day:expr, [$($parse:ident)?], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            $crate::solution!(@setup);
+            let part = requested_part(&[$( $part ),*]);
+            let input = $crate::template::read_file("inputs", DAY);
+            $( run_parse($parse, &input); )?
+            $(
+                if part.is_none_or(|p| p == $part) {
+                    run_part($func, &input, DAY, $part);
+                }
+            )*
+            $crate::template::explain::finish();
+        }
+    };
+
+    (@variants $day:expr, [$($parse:ident)?], $( [$part:expr, [$($func:ident),+]] )*) => {
+        $crate::solution!(@header $day);
+
         fn main() {
             use $crate::template::runner::*;
+            $crate::solution!(@setup);
+            let part = requested_part(&[$( $part ),*]);
             let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $( run_parse($parse, &input); )?
+            $(
+                if part.is_none_or(|p| p == $part) {
+                    run_variants(
+                        &[$( (stringify!($func), &$func as &dyn Fn(&str) -> Option<_>) ),+],
+                        &input,
+                        DAY,
+                        $part,
+                    );
+                }
+            )*
+            $crate::template::explain::finish();
         }
     };
 }
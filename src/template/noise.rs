@@ -0,0 +1,107 @@
+/// Helpers that reduce and detect benchmark noise caused by the host system.
+use std::{fs, process::Command, thread};
+
+/// Settings of the opt-in stable benchmark mode.
+#[derive(Clone, Copy, Debug)]
+pub struct NoiseControl {
+    /// Core the benchmark is pinned to. Defaults to the last core, which the OS tends to schedule the least work on.
+    pub core: Option<usize>,
+    /// Number of rounds that days and parts are interleaved across.
+    pub rounds: usize,
+}
+
+pub const DEFAULT_ROUNDS: usize = 5;
+
+impl NoiseControl {
+    pub fn core(&self) -> usize {
+        self.core.unwrap_or_else(|| {
+            thread::available_parallelism().map_or(0, |x| x.get().saturating_sub(1))
+        })
+    }
+}
+
+/// Pins the current process (and with it, all solution binaries spawned from it) to `core`.
+pub fn pin_to_core(core: usize) -> Result<(), String> {
+    if !cfg!(target_os = "linux") {
+        return Err("CPU pinning is only supported on Linux.".into());
+    }
+
+    let output = Command::new("taskset")
+        .args(["-cp", &core.to_string(), &std::process::id().to_string()])
+        .output()
+        .map_err(|_| "command \"taskset\" not found or not callable.".to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Returns warnings about system state that is known to skew benchmarks.
+pub fn check_system(core: usize) -> Vec<String> {
+    let mut warnings = vec![];
+
+    if !cfg!(target_os = "linux") {
+        return warnings;
+    }
+
+    let governor_path = format!("/sys/devices/system/cpu/cpu{core}/cpufreq/scaling_governor");
+    if let Ok(governor) = fs::read_to_string(governor_path) {
+        let governor = governor.trim();
+        if governor != "performance" {
+            warnings.push(format!(
+                "CPU frequency governor of core {core} is `{governor}`, not `performance`."
+            ));
+        }
+    }
+
+    if let Some(load) = fs::read_to_string("/proc/loadavg")
+        .ok()
+        .and_then(|s| s.split_whitespace().next()?.parse::<f64>().ok())
+    {
+        if load > 1.0 {
+            warnings.push(format!(
+                "load average is {load:.2}, other processes might compete for the CPU."
+            ));
+        }
+    }
+
+    warnings
+}
+
+/// Returns the mean and the coefficient of variation (in percent) of a set of measurements.
+pub fn mean_and_variation(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0_f64, 0_f64);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+
+    if values.len() < 2 || mean == 0_f64 {
+        return (mean, 0_f64);
+    }
+
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1_f64);
+    (mean, variance.sqrt() / mean * 100_f64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::mean_and_variation;
+
+    #[test]
+    fn computes_mean_and_variation() {
+        let (mean, cv) = mean_and_variation(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert!((mean - 5.0).abs() < 1e-9);
+        assert!((cv - 42.761_798).abs() < 1e-4);
+    }
+
+    #[test]
+    fn handles_single_measurements() {
+        assert_eq!(mean_and_variation(&[3.0]), (3.0, 0.0));
+        assert_eq!(mean_and_variation(&[]), (0.0, 0.0));
+    }
+}
@@ -1,106 +1,286 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, str::FromStr, time::Duration};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::config;
+use crate::template::error::{Context, Error, Result};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::{parse_duration, Timing, Timings};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
+pub struct TablePosition {
+    pos_start: usize,
+    pos_end: usize,
+}
+
+/// A column of the benchmark table, next to the leading `Day` column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Parse,
+    Part1,
+    Part2,
+    Total,
+    Memory,
+    Samples,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+impl FromStr for Column {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s
+            .trim()
+            .to_lowercase()
+            .replace(['_', '-', ' '], "")
+            .as_str()
+        {
+            "parse" => Ok(Column::Parse),
+            "part1" | "1" => Ok(Column::Part1),
+            "part2" | "2" => Ok(Column::Part2),
+            "total" => Ok(Column::Total),
+            "memory" | "mem" => Ok(Column::Memory),
+            "samples" => Ok(Column::Samples),
+            x => Err(Error::Usage(format!("unknown benchmark column `{x}`."))),
+        }
     }
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+impl Column {
+    fn title(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Total => "Total",
+            Column::Memory => "Memory",
+            Column::Samples => "Samples",
+        }
+    }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    Day,
+    Slowest,
+}
+
+/// Unit that all durations in the table are converted to. `Auto` keeps the unit picked by the runner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unit {
+    Auto,
+    Nanos,
+    Micros,
+    Millis,
+    Seconds,
+}
+
+impl FromStr for Unit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "auto" => Ok(Unit::Auto),
+            "ns" => Ok(Unit::Nanos),
+            "µs" | "us" => Ok(Unit::Micros),
+            "ms" => Ok(Unit::Millis),
+            "s" => Ok(Unit::Seconds),
+            x => Err(Error::Usage(format!("unknown benchmark unit `{x}`."))),
+        }
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+impl Unit {
+    fn format(self, nanos: f64) -> String {
+        match self {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Unit::Auto => format!("{:.1?}", Duration::from_nanos(nanos as u64)),
+            Unit::Nanos => format!("{nanos:.0}ns"),
+            Unit::Micros => format!("{:.2}µs", nanos / 1000_f64),
+            Unit::Millis => format!("{:.2}ms", nanos / 1_000_000_f64),
+            Unit::Seconds => format!("{:.2}s", nanos / 1_000_000_000_f64),
+        }
+    }
+}
+
+/// Controls where the benchmark table is written to and how it is laid out.
+///
+/// The defaults reproduce the table in `README.md`, every option can be overridden in the `[benchmarks]`
+/// section of `aoc.toml`.
+#[derive(Clone, Debug)]
+pub struct TableConfig {
+    pub path: String,
+    pub marker: String,
+    pub heading_level: usize,
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+    pub unit: Unit,
+    pub budget_nanos: Option<f64>,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            path: "README.md".into(),
+            marker: MARKER.into(),
+            heading_level: 2,
+            columns: vec![Column::Part1, Column::Part2],
+            sort: SortOrder::Day,
+            unit: Unit::Auto,
+            budget_nanos: None,
+        }
+    }
+}
+
+impl TableConfig {
+    fn is_over_budget(&self, timing: &Timing) -> bool {
+        self.budget_nanos
+            .is_some_and(|budget| timing.total_nanos > budget)
+    }
+
+    fn format_duration(&self, duration: Option<&String>) -> String {
+        match (self.unit, duration) {
+            (_, None) => "-".into(),
+            (Unit::Auto, Some(x)) => x.clone(),
+            (unit, Some(x)) => parse_duration(x).map_or_else(|| x.clone(), |n| unit.format(n)),
+        }
+    }
+
+    fn format_cell(&self, column: Column, timing: &Timing) -> String {
+        match column {
+            Column::Parse => self.format_duration(timing.parse.as_ref()),
+            Column::Part1 => self.format_duration(timing.part_1.as_ref()),
+            Column::Part2 => self.format_duration(timing.part_2.as_ref()),
+            Column::Total => self.unit.format(timing.total_nanos),
+            Column::Memory => timing.memory.clone().unwrap_or_else(|| "-".into()),
+            Column::Samples => timing.samples.map_or_else(|| "-".into(), |x| x.to_string()),
+        }
+    }
+}
+
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Other(format!(
+            "too many occurrences of the benchmark marker `{marker}`."
+        )));
     }
 
     let pos_start = matches
         .first()
         .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+        .ok_or_else(|| Error::Other("Could not find table start position.".into()))?;
 
     let pos_end = matches
         .last()
         .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+        .ok_or_else(|| Error::Other("Could not find table end position.".into()))?;
 
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(config: &TableConfig, timings: Timings, total_millis: f64) -> String {
+    let header = format!("{} Benchmarks", "#".repeat(config.heading_level));
+
+    let titles: Vec<&str> = std::iter::once("Day")
+        .chain(config.columns.iter().map(|c| c.title()))
+        .collect();
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![config.marker.clone(), header, String::new()];
+
+    if let Some(machine) = &timings.machine {
+        lines.push(format!("_Measured on {machine}._"));
+        lines.push(String::new());
+    }
 
-    for timing in timings.data {
+    lines.push(format!("| {} |", titles.join(" | ")));
+    lines.push(format!("| {}  |", vec![":---:"; titles.len()].join(" | ")));
+
+    let mut data = timings.data;
+    match config.sort {
+        SortOrder::Day => data.sort_by_key(|t| t.day),
+        SortOrder::Slowest => data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos)),
+    }
+
+    for timing in &data {
         let path = get_path_for_bin(timing.day);
+        let warning = if config.is_over_budget(timing) {
+            " ⚠️"
+        } else {
+            ""
+        };
+
+        let cells: Vec<String> = config
+            .columns
+            .iter()
+            .map(|c| format!("`{}`", config.format_cell(*c, timing)))
+            .collect();
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}){} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            warning,
+            cells.join(" | ")
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+
+    if let Some(budget) = config.budget_nanos {
+        let over_budget = data.iter().filter(|t| config.is_over_budget(t)).count();
+        if over_budget > 0 {
+            lines.push(String::new());
+            lines.push(format!(
+                "⚠️ {over_budget} day(s) over the budget of {} per day.",
+                config.unit.format(budget)
+            ));
+        }
+    }
+
+    lines.push(config.marker.clone());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    config: &TableConfig,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<()> {
+    let positions = locate_table(s, &config.marker)?;
+    let table = construct_table(config, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+pub fn update(timings: Timings) -> Result<()> {
+    let config = &config::get().benchmarks;
+
+    // a dedicated benchmark file is created on first use, the readme is expected to exist.
+    let mut readme = match fs::read(&config.path) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound && config.path != "README.md" => {
+            format!("{}\n{}\n", config.marker, config.marker)
+        }
+        Err(e) => return Err(e).context(format!("Failed to read \"{}\"", config.path)),
+    };
+
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    update_content(&mut readme, config, timings, total_millis)?;
+    fs::write(&config.path, &readme).context(format!("Failed to write \"{}\"", config.path))?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, Column, SortOrder, TableConfig, Unit, MARKER};
+    use crate::{
+        day, template::machine::Machine, template::timings::Timing, template::timings::Timings,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -110,20 +290,33 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
                 },
             ],
+            machine: None,
         }
     }
 
@@ -131,28 +324,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &TableConfig::default(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &TableConfig::default(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &TableConfig::default(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &TableConfig::default(), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &TableConfig::default(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +353,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &TableConfig::default(), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +373,68 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_config() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let config = TableConfig {
+            heading_level: 3,
+            columns: vec![Column::Part1, Column::Total, Column::Samples],
+            sort: SortOrder::Slowest,
+            unit: Unit::Millis,
+            budget_nanos: Some(5e+10),
+            ..TableConfig::default()
+        };
+        update_content(&mut s, &config, get_mock_timings(), 190.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "### Benchmarks",
+            "",
+            "| Day | Part 1 | Total | Samples |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 4](./src/bin/04.rs) ⚠️ | `40.00ms` | `90000.00ms` | `-` |",
+            "| [Day 2](./src/bin/02.rs) ⚠️ | `30.00ms` | `70000.00ms` | `-` |",
+            "| [Day 1](./src/bin/01.rs) | `10.00ms` | `30000.00ms` | `-` |",
+            "",
+            "**Total: 190.00ms**",
+            "",
+            "⚠️ 2 day(s) over the budget of 50000.00ms per day.",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_custom_marker() {
+        let marker = "<!--- benchmarks --->";
+        let mut s = format!("{}{}", marker, marker);
+        let config = TableConfig {
+            marker: marker.into(),
+            ..TableConfig::default()
+        };
+        update_content(&mut s, &config, get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(marker).count(), 2);
+        assert_eq!(s.contains(MARKER), false);
+    }
+
+    #[test]
+    fn format_benchmarks_with_machine() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let timings = Timings {
+            machine: Some(Machine {
+                cpu: "Apple M1".into(),
+                cores: 8,
+                rustc: "rustc 1.83.0".into(),
+                profile: "release".into(),
+                threads: 8,
+            }),
+            ..get_mock_timings()
+        };
+        update_content(&mut s, &TableConfig::default(), timings, 190.0).unwrap();
+        assert_eq!(
+            s.lines().nth(3),
+            Some("_Measured on Apple M1 (8 cores, 8 threads), rustc 1.83.0, release profile._")
+        );
+    }
 }
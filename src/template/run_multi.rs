@@ -1,62 +1,356 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{HashMap, HashSet},
+    io::{stdout, Write},
+    path::Path,
+    sync::{mpsc, Mutex},
+    thread,
+    time::Duration,
+};
 
+use crate::template::error::{Error, Result};
+use crate::template::runner::BENCH_BUDGET_MILLIS;
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    fingerprint::fingerprint,
+    machine::Machine,
+    noise::mean_and_variation,
+    timings::{parse_duration, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Settings that are passed on to the solution binaries.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    pub is_release: bool,
+    pub is_timed: bool,
+    /// Size of the global rayon thread pool, defaults to the number of available cores.
+    pub threads: Option<usize>,
+    /// Only run this part of each solution, defaults to all parts.
+    pub part: Option<u8>,
+}
+
+impl RunOptions {
+    fn cargo_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.is_release {
+            args.push("--release".into());
+        }
+
+        args
+    }
+
+    fn bin_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.is_timed {
+            // mirror `--time` flag to child invocations.
+            args.push("--time".into());
+        }
+
+        if let Some(threads) = self.threads {
+            args.push("--threads".into());
+            args.push(threads.to_string());
+        }
+
+        if let Some(part) = self.part {
+            args.push("--part".into());
+            args.push(part.to_string());
+        }
+
+        args
+    }
+}
+
+pub fn run_multi(days_to_run: &HashSet<Day>, options: &RunOptions) -> Result<Option<Timings>> {
+    let machine = Machine::detect(options.is_release, options.threads);
+    run_multi_in(None, days_to_run, options, &machine)
+}
+
+/// Like [`run_multi`], but runs the solutions of the checkout in `workdir` if passed.
+/// Timings of other checkouts carry no content hash since it would describe the current tree.
+/// The timings are tagged with `machine`, which `cargo time` detects once after pinning the process.
+pub fn run_multi_in(
+    workdir: Option<&Path>,
+    days_to_run: &HashSet<Day>,
+    options: &RunOptions,
+    machine: &Machine,
+) -> Result<Option<Timings>> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut failed: Vec<Day> = vec![];
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let hash = workdir.map_or_else(|| fingerprint(day), |_| None);
+        let output = child_commands::run_solution(workdir, day, options)?;
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+        if !output.success {
+            failed.push(day);
+        }
+
+        if output.stdout.is_empty() {
+            println!("Not solved.");
+        } else {
+            let mut val = child_commands::parse_exec_time(&output.stdout, day);
+            val.hash = hash;
+            timings.push(val);
+        }
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    check_failed(&failed)?;
+
+    if options.is_timed {
+        let timings = Timings {
+            data: timings,
+            machine: Some(machine.clone()),
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+        Ok(Some(timings))
     } else {
-        None
+        Ok(None)
     }
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
+/// Fails if the solutions of any day failed to build or run. Their errors were already printed.
+fn check_failed(failed: &[Day]) -> Result<()> {
+    if failed.is_empty() {
+        return Ok(());
+    }
+
+    let days: Vec<String> = failed.iter().map(ToString::to_string).collect();
+    Err(Error::Solution(format!(
+        "the solutions of day(s) {} failed.",
+        days.join(", ")
+    )))
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+/// Runs days on `jobs` concurrent workers. The output of each day is buffered and printed in day order.
+/// Timings of concurrent runs are not meaningful, so this is only available for untimed runs.
+pub fn run_multi_parallel(
+    days_to_run: &HashSet<Day>,
+    options: &RunOptions,
+    jobs: usize,
+) -> Result<()> {
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    child_commands::build_solutions(options)?;
+
+    // NOTE: reversed, so that popping from the queue yields the earliest day first.
+    let queue = Mutex::new(days.iter().rev().copied().collect::<Vec<Day>>());
+    let (tx, rx) = mpsc::channel();
+    let mut failed: Vec<Day> = vec![];
+
+    thread::scope(|scope| -> Result<()> {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            let (queue, tx) = (&queue, tx.clone());
+            scope.spawn(move || {
+                while let Some(day) = queue.lock().unwrap().pop() {
+                    let output = child_commands::run_solution_buffered(day, options);
+                    // NOTE: the receiver only hangs up early if another worker failed.
+                    if tx.send((day, output)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(tx);
+
+        let mut finished = HashMap::new();
+        let mut next = 0;
+
+        for (day, output) in rx {
+            finished.insert(day, output?);
+
+            while let Some(output) = days.get(next).and_then(|d| finished.remove(d)) {
+                if next > 0 {
+                    println!();
+                }
+
+                println!("{ANSI_BOLD}Day {}{ANSI_RESET}", days[next]);
+                println!("------");
+
+                output.stderr.iter().for_each(|line| eprintln!("{line}"));
+                if output.stdout.is_empty() {
+                    println!("Not solved.");
+                } else {
+                    output.stdout.iter().for_each(|line| println!("{line}"));
+                }
+
+                if !output.success {
+                    failed.push(days[next]);
+                }
+
+                next += 1;
+            }
+        }
+
+        Ok(())
+    })?;
+
+    check_failed(&failed)
+}
+
+/// Benches days in `rounds` short rounds instead of one burst per day, rotating the order of days every round.
+/// Reports the mean and the run-to-run variation of each part across rounds.
+pub fn run_multi_interleaved(
+    workdir: Option<&Path>,
+    days_to_run: &HashSet<Day>,
+    rounds: usize,
+    options: &RunOptions,
+    machine: &Machine,
+) -> Result<Timings> {
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+    let budget_millis = (BENCH_BUDGET_MILLIS / rounds.max(1) as u64).max(1);
+
+    let mut results: Vec<Vec<Timing>> = vec![vec![]; days.len()];
+    let mut failed: Vec<Day> = vec![];
+    let mut stdout = stdout();
+
+    for round in 0..rounds {
+        print!("\rRound {}/{rounds}", round + 1);
+        let _ = stdout.flush();
+
+        for i in 0..days.len() {
+            let index = (i + round) % days.len();
+            let day = days[index];
+
+            if failed.contains(&day) {
+                continue;
+            }
+
+            // a failing day is skipped in later rounds, the remaining days are still benched.
+            let output = match child_commands::run_bench(workdir, day, budget_millis, options) {
+                Ok(output) => output,
+                Err(Error::Solution(_)) => {
+                    failed.push(day);
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            if !output.is_empty() {
+                results[index].push(child_commands::parse_exec_time(&output, day));
+            }
+        }
     }
+
+    println!("\r{}", " ".repeat(16));
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+    let mut need_space = false;
+
+    for (day, rounds) in days.into_iter().zip(results) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        if failed.contains(&day) {
+            println!("Failed.");
+            continue;
+        }
+
+        if rounds.is_empty() {
+            println!("Not solved.");
+            continue;
+        }
+
+        let mut timing = Timing {
+            day,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            parse: None,
+            memory: None,
+            samples: rounds.iter().map(|t| t.samples).sum(),
+            hash: workdir.map_or_else(|| fingerprint(day), |_| None),
+        };
+
+        let parse = mean_duration(rounds.iter().filter_map(|t| t.parse.as_deref()));
+        if let Some((mean, variation, n)) = &parse {
+            println!(
+                "Parse: {mean_str} {ANSI_ITALIC}± {variation:.1}% @ {n} rounds{ANSI_RESET}",
+                mean_str = format_nanos(*mean)
+            );
+        }
+        timing.parse = parse.map(|(mean, ..)| format_nanos(mean));
+
+        for part in [1, 2] {
+            let values = rounds
+                .iter()
+                .filter_map(|t| if part == 1 { &t.part_1 } else { &t.part_2 }.as_deref());
+
+            let Some((mean, variation, n)) = mean_duration(values) else {
+                println!("Part {part}: ✖");
+                continue;
+            };
+
+            let mean_str = format_nanos(mean);
+            println!(
+                "Part {part}: {mean_str} {ANSI_ITALIC}± {variation:.1}% @ {n} rounds{ANSI_RESET}"
+            );
+
+            timing.total_nanos += mean;
+            if part == 1 {
+                timing.part_1 = Some(mean_str);
+            } else {
+                timing.part_2 = Some(mean_str);
+            }
+        }
+
+        timing.memory = rounds.iter().fold(None, |acc, t| {
+            child_commands::max_memory(acc, t.memory.clone())
+        });
+
+        timings.push(timing);
+    }
+
+    failed.sort();
+    check_failed(&failed)?;
+
+    let timings = Timings {
+        data: timings,
+        machine: Some(machine.clone()),
+    };
+    let total_millis = timings.total_millis();
+    println!("\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+    Ok(timings)
+}
+
+/// Mean and coefficient of variation of durations printed by the runner, with the number of values.
+fn mean_duration<'a>(durations: impl Iterator<Item = &'a str>) -> Option<(f64, f64, usize)> {
+    let values: Vec<f64> = durations.filter_map(parse_duration).collect();
+
+    if values.is_empty() {
+        return None;
+    }
+
+    let (mean, variation) = mean_and_variation(&values);
+    Some((mean, variation, values.len()))
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
 }
 
 #[must_use]
@@ -67,65 +361,203 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::{get_path_for_bin, RunOptions};
+    use crate::template::error::{Context, Error, Result};
+    use crate::template::timings::{parse_duration, parse_memory};
     use crate::template::Day;
     use std::{
+        env,
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
+        sync::Arc,
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Output of a solution bin. `stderr` is empty if it was echoed while running.
+    #[derive(Debug, Default)]
+    pub struct Output {
+        pub stdout: Vec<String>,
+        pub stderr: Vec<String>,
+        /// Whether the solution built and ran successfully.
+        pub success: bool,
+    }
+
+    /// Receives the stdout and stderr lines of a solution while it runs.
+    pub type OnLine = Arc<dyn Fn(String) + Send + Sync>;
+
+    /// What happens to the output of a solution while it runs.
+    enum Forward {
+        /// Print it to stdout and stderr.
+        Echo,
+        /// Only capture it.
+        Buffer,
+        /// Pass each line to a callback.
+        Stream(OnLine),
+    }
+
+    /// Run the solution bin for a given day, optionally in the checkout at `workdir`.
+    pub fn run_solution(workdir: Option<&Path>, day: Day, options: &RunOptions) -> Result<Output> {
+        run(workdir, day, options, &[], Forward::Echo)
+    }
+
+    /// Run the solution bin for a given day, passing each output line to `on_line` as it is printed.
+    pub fn run_solution_streamed(
+        day: Day,
+        options: &RunOptions,
+        on_line: OnLine,
+    ) -> Result<Output> {
+        run(None, day, options, &[], Forward::Stream(on_line))
+    }
+
+    /// Bench the release build of a day, spending about `budget_millis` on each part.
+    /// The output is captured, but not forwarded to stdout.
+    pub fn run_bench(
+        workdir: Option<&Path>,
+        day: Day,
+        budget_millis: u64,
+        options: &RunOptions,
+    ) -> Result<Vec<String>> {
+        let options = RunOptions {
+            is_release: true,
+            is_timed: true,
+            ..*options
+        };
+
+        let args = ["--bench-budget".to_string(), budget_millis.to_string()];
+        let output = run(workdir, day, &options, &args, Forward::Buffer)?;
+        output.stderr.iter().for_each(|line| eprintln!("{line}"));
+
+        if !output.success {
+            return Err(Error::Solution(format!("benchmarking day {day} failed.")));
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        Ok(output.stdout)
+    }
+
+    /// Run the solution bin for a given day, buffering stdout and stderr instead of forwarding them.
+    pub fn run_solution_buffered(day: Day, options: &RunOptions) -> Result<Output> {
+        run(None, day, options, &[], Forward::Buffer)
+    }
+
+    /// Build all solution bins, so that concurrent runs do not wait on each other's compilation.
+    pub fn build_solutions(options: &RunOptions) -> Result<()> {
+        // NOTE: a failing build is reported by the individual runs.
+        Command::new("cargo")
+            .args(["build", "--quiet", "--bins"])
+            .args(options.cargo_args())
+            .status()
+            .map_err(|_| missing_cargo())?;
+        Ok(())
+    }
 
-        if is_release {
-            args.push("--release");
+    fn missing_cargo() -> Error {
+        Error::MissingTool {
+            tool: "cargo",
+            hint: None,
         }
+    }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+    /// Returns the stdout lines and, unless they are echoed, the stderr lines of the child.
+    fn run(
+        workdir: Option<&Path>,
+        day: Day,
+        options: &RunOptions,
+        extra_args: &[String],
+        forward: Forward,
+    ) -> Result<Output> {
+        let bin_path = get_path_for_bin(day);
+        let bin_path = workdir.map_or_else(
+            || Path::new(&bin_path).to_path_buf(),
+            |dir| dir.join(&bin_path),
+        );
+
+        // skip command invocation for days that have not been scaffolded yet.
+        if !bin_path.exists() {
+            return Ok(Output {
+                success: true,
+                ..Output::default()
+            });
         }
 
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
+        args.extend(options.cargo_args());
+        args.push("--".into());
+        args.extend(options.bin_args());
+        args.extend_from_slice(extra_args);
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output while grabbing stdout lines, or grab both if it is only buffered.
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args);
+
+        if let Some(workdir) = workdir {
+            // share one build directory between checkouts so dependencies are only compiled once.
+            let target_dir = env::current_dir()
+                .context("Failed to read the current directory")?
+                .join("target")
+                .join("worktree");
+            cmd.current_dir(workdir).env("CARGO_TARGET_DIR", target_dir);
+        }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let mut cmd = cmd
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .spawn()?;
+            .spawn()
+            .map_err(|_| missing_cargo())?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let broken_pipe = || Error::Other(format!("Failed to read the output of day {day}."));
+        let stdout = BufReader::new(cmd.stdout.take().ok_or_else(broken_pipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or_else(broken_pipe)?);
 
         let mut output = vec![];
 
+        let on_line = match &forward {
+            Forward::Stream(on_line) => Some(on_line.clone()),
+            _ => None,
+        };
+        let echo = matches!(forward, Forward::Echo);
+
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            let mut lines = vec![];
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                if echo {
+                    eprintln!("{line}");
+                } else {
+                    if let Some(on_line) = &on_line {
+                        on_line(line.clone());
+                    }
+                    lines.push(line);
+                }
             });
+            lines
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
+        for line in stdout.lines().map_while(Result::ok) {
+            match &forward {
+                Forward::Echo => println!("{line}"),
+                Forward::Stream(on_line) => on_line(line.clone()),
+                Forward::Buffer => {}
+            }
             output.push(line);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let errors = thread.join().unwrap_or_default();
+        let status = cmd
+            .wait()
+            .context(format!("Failed to wait for the solution of day {day}"))?;
 
-        Ok(output)
+        Ok(Output {
+            stdout: output,
+            stderr: errors,
+            success: status.success(),
+        })
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -134,24 +566,35 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            parse: None,
+            memory: None,
+            samples: None,
+            hash: None,
         };
 
         output
             .iter()
             .filter_map(|l| {
-                if !l.contains(" samples)") {
+                // lines of captured solution output are indented below the result line.
+                if !l.contains(" samples)") || l.starts_with("  │") {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some((timing_str, nanos, samples)) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, samples, parse_peak_memory(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, samples, memory)| {
+                if part.contains("Parse") {
+                    // the parts parse the input again, their timings already include it.
+                    timings.parse = Some(timing_str.into());
+                    return;
+                }
+
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
                 } else if part.contains("Part 2") {
@@ -159,34 +602,46 @@ pub mod child_commands {
                 }
 
                 timings.total_nanos += nanos;
+                timings.samples = Some(timings.samples.map_or(samples, |s| s.min(samples)));
+                timings.memory = max_memory(timings.memory.take(), memory);
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// The peak memory that follows the timing of a result line, e.g. `(1.2ms @ 10 samples) · peak 1.3 MiB`.
+    fn parse_peak_memory(line: &str) -> Option<String> {
+        let memory = line
+            .split(" samples)")
+            .nth(1)?
+            .split("peak ")
+            .nth(1)?
+            .trim();
+        parse_memory(memory).map(|_| memory.to_string())
+    }
+
+    /// The larger of two amounts of memory as printed by the runner.
+    pub fn max_memory(a: Option<String>, b: Option<String>) -> Option<String> {
+        match (a, b) {
+            (Some(a), Some(b)) if parse_memory(&b) > parse_memory(&a) => Some(b),
+            (Some(a), _) => Some(a),
+            (None, b) => b,
+        }
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
+    fn parse_time(line: &str) -> Option<(&str, f64, u64)> {
+        let mut segments = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+            .next_back()?
+            .split('@');
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let str_timing = segments.next()?.trim();
+        let samples = segments.next()?.trim().parse().ok()?;
+        let parsed_timing = parse_duration(str_timing)?;
 
-        Some((str_timing, parsed_timing))
+        Some((str_timing, parsed_timing, samples))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -222,6 +677,7 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.samples, Some(99999));
         }
 
         #[test]
@@ -239,6 +695,39 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_parse_time_and_memory() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (12.0µs @ 10000 samples) · peak 64.0 KiB".into(),
+                    "Part 1: 42 (2ms @ 5 samples) · peak 1.5 MiB".into(),
+                    "Part 2: 43 (1ms @ 10 samples) · peak 512.0 KiB".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 3000000_f64);
+            assert_eq!(res.parse.unwrap(), "12.0µs");
+            assert_eq!(res.memory.unwrap(), "1.5 MiB");
+            assert_eq!(res.samples, Some(5));
+        }
+
+        #[test]
+        fn ignores_captured_output() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 42 (2ms @ 5 samples)".into(),
+                    "  ┌ stdout (1 line)".into(),
+                    "  │ Part 2: 1 (1s @ 1 samples)".into(),
+                    "  └".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "2ms");
+            assert!(res.part_2.is_none());
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -2,54 +2,262 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::capture::{capture, suppress, Captured};
+use crate::template::error::Error;
+use crate::template::timings::format_memory;
+use crate::template::{alloc, aoc_cli, explain, log, spans, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{ANSI_BOLD, ANSI_CLEAR_LINE};
+
+/// Configure the global rayon thread pool if the `--threads <n>` argument was passed.
+pub fn configure_threads() {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--threads") else {
+        return;
+    };
+
+    let Some(threads) = args.get(index + 1).and_then(|x| x.parse::<usize>().ok()) else {
+        Error::Usage("Unexpected command-line input. Format: cargo solve 1 --threads 4".into())
+            .exit();
+    };
+
+    if let Err(e) = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+    {
+        Error::Other(format!("Failed to configure thread pool: {e}")).exit();
+    }
+}
+
+/// Parse the `--part <n>` argument that restricts a run to a single part, exits if it names a part that
+/// is not enabled in the `solution!` macro.
+pub fn requested_part(enabled_parts: &[u8]) -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--part")?;
+
+    match args.get(index + 1).and_then(|x| x.parse::<u8>().ok()) {
+        Some(part) if enabled_parts.contains(&part) => Some(part),
+        Some(part) => Error::Usage(format!(
+            "Part {part} is not enabled for this day. Check the `solution!` macro."
+        ))
+        .exit(),
+        None => {
+            Error::Usage("Unexpected command-line input. Format: cargo solve 1 --part 1".into())
+                .exit()
+        }
+    }
+}
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    log::set_context(day, part);
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    explain::start(part);
+    let (result, measured, captured) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &format_run_duration(&measured));
+    print_captured(&captured);
+    explain::print_part(part);
+    spans::print_part();
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Run the parse function named in the `solution!` macro on its own. Parts usually parse the input
+/// themselves, so the parse time is reported separately and not added to the total.
+pub fn run_parse<T>(func: impl Fn(&str) -> T, input: &str) {
+    let (_, measured, captured) = run_timed(func, input, |_| print!("Parse:"));
+
+    print!("\r{ANSI_CLEAR_LINE}");
+    println!("Parse:{}", format_run_duration(&measured));
+    print_captured(&captured);
+}
+
+/// A named implementation of a solution part.
+pub type Variant<'a, T> = (&'a str, &'a dyn Fn(&str) -> Option<T>);
+
+/// Run all variants of a solution part and compare their timings.
+/// The last variant is the primary one: its answer is submitted and its timing is reported to `cargo time`.
+/// Exits with an error if the variants disagree on the answer.
+pub fn run_variants<T: Display + PartialEq>(
+    variants: &[Variant<T>],
+    input: &str,
+    day: Day,
+    part: u8,
+) {
+    if let [(_, func)] = variants {
+        run_part(func, input, day, part);
+        return;
+    }
+
+    log::set_context(day, part);
+    let part_str = format!("Part {part}");
+    let mut runs = Vec::with_capacity(variants.len());
+
+    for (name, func) in variants {
+        // only the events of the primary variant are kept.
+        explain::start(part);
+        let (result, measured, captured) = run_timed(func, input, |_| {
+            print!("\r{ANSI_CLEAR_LINE}{part_str}: {ANSI_ITALIC}{name}{ANSI_RESET}");
+            let _ = stdout().flush();
+        });
+        runs.push((*name, result, measured, captured));
+    }
+
+    let Some((_, primary, measured, _)) = runs.last() else {
+        return;
+    };
+
+    print!("\r{ANSI_CLEAR_LINE}");
+    print_result(primary, &part_str, &format_run_duration(measured));
+    print_variants(&runs, primary);
+    explain::print_part(part);
+    spans::print_part();
+
+    for (name, _, _, captured) in &runs {
+        if !captured.is_empty() {
+            println!("  {ANSI_ITALIC}{name}{ANSI_RESET}");
+            print_captured(captured);
+        }
+    }
+
+    if runs.iter().any(|(_, result, ..)| result != primary) {
+        Error::Solution(format!("{part_str}: variants disagree on the answer.")).exit();
+    }
+
+    if let Some(result) = primary {
+        submit_result(result, day, part);
+    }
+}
+
+/// Name, result, measurements and output of a benched variant.
+type VariantRun<'a, T> = (&'a str, Option<T>, Measured, Captured);
+
+/// How long a part took and how much memory its first execution allocated at peak.
+struct Measured {
+    duration: Duration,
+    samples: u128,
+    memory: Option<u64>,
+}
+
+/// Print the timings of all variants relative to the fastest one.
+/// NOTE: rows must not end in `samples)`, `cargo time` would pick them up as the timing of the part.
+fn print_variants<T: Display + PartialEq>(runs: &[VariantRun<T>], primary: &Option<T>) {
+    let width = runs.iter().map(|(name, ..)| name.len()).max().unwrap_or(0);
+    let fastest = runs
+        .iter()
+        .map(|(_, _, measured, _)| measured.duration.as_nanos())
+        .min()
+        .unwrap_or(0)
+        .max(1);
+
+    for (name, result, Measured { duration, .. }, _) in runs {
+        #[allow(clippy::cast_precision_loss)]
+        let relative = duration.as_nanos() as f64 / fastest as f64;
+        let relative_str = if duration.as_nanos() <= fastest {
+            "fastest".to_string()
+        } else {
+            format!("{relative:.2}x slower")
+        };
+
+        let mismatch = if result == primary {
+            String::new()
+        } else {
+            match result {
+                Some(result) => format!("  ✖ answer differs: {ANSI_BOLD}{result}{ANSI_RESET}"),
+                None => "  ✖ no answer".into(),
+            }
+        };
+
+        println!(
+            "  {ANSI_ITALIC}{name:<width$}{ANSI_RESET}{:>12}  {relative_str}{mismatch}",
+            format!("{duration:.1?}")
+        );
+    }
+}
+
+/// Rounds of interleaved benchmarks are aggregated by the parent process, keep the precision.
+/// The peak memory is only reported to `cargo time`, it follows the timing so that `samples)` still ends it.
+fn format_run_duration(measured: &Measured) -> String {
+    let Measured {
+        duration,
+        samples,
+        memory,
+    } = measured;
+
+    let mut formatted = if bench_budget().is_some() {
+        format!(" ({duration:.3?} @ {samples} samples)")
+    } else {
+        format_duration(duration, *samples)
+    };
+
+    if let Some(memory) = memory.filter(|_| *samples > 1) {
+        formatted.push_str(&format!(" · peak {}", format_memory(memory)));
+    }
+
+    formatted
+}
+
+/// Print what a part wrote to stdout and stderr below its result line.
+/// Output is not collapsed if `debug!` or `trace!` messages were requested with `-v` or `-vv`.
+fn print_captured(captured: &Captured) {
+    let collapsed = !env::args().any(|x| x == "--show-output") && !log::enabled(1);
+
+    for line in captured.format(collapsed) {
+        println!("{line}");
+    }
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Output of the first execution is captured and returned, output of benchmark iterations is discarded.
+/// The peak memory is measured during the first execution.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        let input = input.clone();
+) -> (T, Measured, Captured) {
+    #[cfg(feature = "dhat-heap")]
+    let profiler = dhat::Profiler::new_heap();
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+    let input_clone = input.clone();
+    let ((result, base_time, memory), captured) = capture(|| {
+        spans::start();
+        let timer = Instant::now();
+        let (result, memory) = alloc::measure(|| func(input_clone));
+        let elapsed = timer.elapsed();
+        spans::stop(elapsed);
+        (result, elapsed, memory)
+    });
+    explain::stop();
 
-        func(input)
-    };
-    let base_time = timer.elapsed();
+    // the heap profile is printed when the profiler is dropped, which must not be captured.
+    #[cfg(feature = "dhat-heap")]
+    drop(profiler);
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let (duration, samples) = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    let measured = Measured {
+        duration,
+        samples,
+        memory,
+    };
+
+    (result, measured, captured)
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
@@ -58,18 +266,21 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
+    let budget = bench_budget().unwrap_or(Duration::from_millis(BENCH_BUDGET_MILLIS));
     let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+        (budget.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
-    for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
-        let timer = Instant::now();
-        black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
-    }
+    suppress(|| {
+        for _ in 0..bench_iterations {
+            // need a clone here to make the borrow checker happy.
+            let cloned = input.clone();
+            let timer = Instant::now();
+            black_box(func(black_box(cloned)));
+            timers.push(timer.elapsed());
+        }
+    });
 
     (
         #[allow(clippy::cast_possible_truncation)]
@@ -78,6 +289,16 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     )
 }
 
+/// Time spent benching each part unless the parent process passes `--bench-budget <millis>`.
+pub const BENCH_BUDGET_MILLIS: u64 = 1000;
+
+/// Parse the `--bench-budget <millis>` argument that limits the time spent benching each part.
+fn bench_budget() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--bench-budget")?;
+    args.get(index + 1)?.parse().ok().map(Duration::from_millis)
+}
+
 fn average_duration(numbers: &[Duration]) -> u128 {
     numbers
         .iter()
@@ -132,38 +353,30 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///
+/// Exits with [`Error::WrongAnswer`] if the answer was rejected.
+fn submit_result<T: Display>(result: T, day: Day, part: u8) {
     let args: Vec<String> = env::args().collect();
 
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
-
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
-
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    let Some(index) = args.iter().position(|x| x == "--submit") else {
+        return;
+    };
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let Some(part_submit) = args.get(index + 1).and_then(|x| x.parse::<u8>().ok()) else {
+        Error::Usage("Unexpected command-line input. Format: cargo solve 1 --submit 1".into())
+            .exit();
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if let Err(e) = aoc_cli::check() {
+        e.exit();
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    if let Err(e) = aoc_cli::submit(day, part, &result.to_string()) {
+        e.exit();
+    }
 }
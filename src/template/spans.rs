@@ -0,0 +1,257 @@
+/// Hierarchical profiler for solution internals, see the `span!` macro.
+use std::cell::RefCell;
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Aggregated timings of all calls of a span at one position in the tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Node {
+    name: &'static str,
+    parent: Option<usize>,
+    calls: u64,
+    inclusive: Duration,
+    exclusive: Duration,
+}
+
+/// A span that is currently open on this thread.
+struct Frame {
+    node: usize,
+    start: Instant,
+    children: Duration,
+}
+
+static REQUESTED: OnceLock<bool> = OnceLock::new();
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static NODES: Mutex<Vec<Node>> = Mutex::new(vec![]);
+static TOTAL: Mutex<Duration> = Mutex::new(Duration::ZERO);
+
+thread_local! {
+    // NOTE: spans opened on rayon worker threads start a new root, their parent lives on another thread.
+    static STACK: RefCell<Vec<Frame>> = const { RefCell::new(vec![]) };
+}
+
+fn requested() -> bool {
+    *REQUESTED.get_or_init(|| env::args().any(|x| x == "--profile-spans"))
+}
+
+/// Guard returned by `span!`, the span is closed when it is dropped.
+#[must_use = "the span is closed immediately if the guard is not bound, use `let _s = span!(..)`"]
+pub struct Span(bool);
+
+impl Span {
+    #[doc(hidden)]
+    #[inline]
+    pub fn enter(name: &'static str) -> Self {
+        if !ACTIVE.load(Ordering::Relaxed) {
+            return Span(false);
+        }
+
+        STACK.with_borrow_mut(|stack| {
+            let parent = stack.last().map(|f| f.node);
+            let mut nodes = NODES.lock().unwrap();
+
+            let node = nodes
+                .iter()
+                .position(|n| n.parent == parent && n.name == name)
+                .unwrap_or_else(|| {
+                    nodes.push(Node {
+                        name,
+                        parent,
+                        calls: 0,
+                        inclusive: Duration::ZERO,
+                        exclusive: Duration::ZERO,
+                    });
+                    nodes.len() - 1
+                });
+
+            stack.push(Frame {
+                node,
+                start: Instant::now(),
+                children: Duration::ZERO,
+            });
+        });
+
+        Span(true)
+    }
+}
+
+impl Drop for Span {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.0 {
+            return;
+        }
+
+        STACK.with_borrow_mut(|stack| {
+            let Some(frame) = stack.pop() else {
+                return;
+            };
+
+            let elapsed = frame.start.elapsed();
+
+            if let Some(parent) = stack.last_mut() {
+                parent.children += elapsed;
+            }
+
+            let mut nodes = NODES.lock().unwrap();
+            let node = &mut nodes[frame.node];
+            node.calls += 1;
+            node.inclusive += elapsed;
+            node.exclusive += elapsed.saturating_sub(frame.children);
+        });
+    }
+}
+
+/// Starts profiling the spans of a part if running with `--profile-spans`.
+pub fn start() {
+    if requested() {
+        NODES.lock().unwrap().clear();
+        ACTIVE.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Stops profiling, e.g. before a part is benched. `total` is the run time of the profiled part.
+pub fn stop(total: Duration) {
+    if ACTIVE.swap(false, Ordering::Relaxed) {
+        *TOTAL.lock().unwrap() = total;
+    }
+}
+
+/// Prints the span tree of the last profiled part if running with `--profile-spans`.
+pub fn print_part() {
+    if !requested() {
+        return;
+    }
+
+    let total = *TOTAL.lock().unwrap();
+    for line in format_tree(&NODES.lock().unwrap(), total) {
+        println!("{line}");
+    }
+}
+
+fn format_tree(nodes: &[Node], total: Duration) -> Vec<String> {
+    if nodes.is_empty() {
+        return vec![];
+    }
+
+    let mut rows: Vec<(usize, &Node)> = vec![];
+    collect_rows(nodes, None, 0, &mut rows);
+
+    let width = rows
+        .iter()
+        .map(|(depth, node)| depth * 2 + node.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let mut lines = vec![format!(
+        "  {ANSI_BOLD}{:<width$}{:>10}{:>12}{:>12}{:>8}{ANSI_RESET}",
+        "Span", "Calls", "Inclusive", "Exclusive", "Share"
+    )];
+
+    for (depth, node) in rows {
+        #[allow(clippy::cast_precision_loss)]
+        let share = node.inclusive.as_nanos() as f64 / total.as_nanos().max(1) as f64 * 100_f64;
+
+        lines.push(format!(
+            "  {:<width$}{:>10}{:>12}{:>12}{:>7.1}%",
+            format!("{}{}", "  ".repeat(depth), node.name),
+            node.calls,
+            format!("{:.1?}", node.inclusive),
+            format!("{:.1?}", node.exclusive),
+            share
+        ));
+    }
+
+    lines.push(format!(
+        "  {ANSI_ITALIC}shares are relative to the part's run time of {total:.1?}{ANSI_RESET}"
+    ));
+
+    lines
+}
+
+/// Collects nodes depth-first, children sorted by inclusive time.
+fn collect_rows<'a>(
+    nodes: &'a [Node],
+    parent: Option<usize>,
+    depth: usize,
+    rows: &mut Vec<(usize, &'a Node)>,
+) {
+    let mut children: Vec<(usize, &Node)> = nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| n.parent == parent)
+        .collect();
+    children.sort_by_key(|(_, n)| std::cmp::Reverse(n.inclusive));
+
+    for (index, node) in children {
+        rows.push((depth, node));
+        collect_rows(nodes, Some(index), depth + 1, rows);
+    }
+}
+
+/// Opens a profiling span that is closed when the returned guard goes out of scope:
+///
+/// ```ignore
+/// let _s = advent_of_code::span!("flood_fill");
+/// ```
+///
+/// Spans are aggregated into a tree that `cargo solve <day> --profile-spans` prints after each part.
+/// Otherwise, opening a span is a no-op.
+#[macro_export]
+macro_rules! span {
+    ($name:expr) => {
+        $crate::template::spans::Span::enter($name)
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_tree, Node};
+
+    fn node(
+        name: &'static str,
+        parent: Option<usize>,
+        calls: u64,
+        inclusive: u64,
+        exclusive: u64,
+    ) -> Node {
+        Node {
+            name,
+            parent,
+            calls,
+            inclusive: Duration::from_millis(inclusive),
+            exclusive: Duration::from_millis(exclusive),
+        }
+    }
+
+    #[test]
+    fn formats_tree() {
+        let nodes = vec![
+            node("parse", None, 1, 2, 2),
+            node("flood_fill", None, 1, 8, 1),
+            node("sides", Some(1), 140, 7, 7),
+        ];
+
+        let lines = format_tree(&nodes, Duration::from_millis(10));
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with("  flood_fill "));
+        assert!(lines[1].ends_with("8.0ms       1.0ms   80.0%"));
+        assert!(lines[2].starts_with("    sides "));
+        assert!(lines[2].contains(" 140 "));
+        assert!(lines[3].starts_with("  parse "));
+        assert!(lines[3].ends_with("20.0%"));
+    }
+
+    #[test]
+    fn skips_empty_trees() {
+        assert!(format_tree(&[], Duration::from_millis(10)).is_empty());
+    }
+}
@@ -0,0 +1,240 @@
+/// Solution templates used by `cargo scaffold`, and the placeholders they can contain.
+use std::path::Path;
+use std::{fs, io};
+
+use crate::template::config;
+use crate::template::error::{Context, Error, Result};
+use crate::template::Day;
+
+/// A template that ships with the template repository.
+pub struct BuiltIn {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub contents: &'static str,
+}
+
+pub const BUILT_IN: &[BuiltIn] = &[
+    BuiltIn {
+        name: "default",
+        about: "Blank `part_one` and `part_two` stubs",
+        contents: include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt")),
+    },
+    BuiltIn {
+        name: "grid",
+        about: "Parses the input into a grid of bytes",
+        contents: include_str!("templates/grid.txt"),
+    },
+    BuiltIn {
+        name: "blocks",
+        about: "Parses blank-line-separated blocks of lines",
+        contents: include_str!("templates/blocks.txt"),
+    },
+    BuiltIn {
+        name: "numbers",
+        about: "Parses the integers of each line",
+        contents: include_str!("templates/numbers.txt"),
+    },
+];
+
+/// Answer type of the parts if neither `--answer-type` nor `aoc.toml` set one.
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// Values substituted into a template.
+///
+/// | Placeholder            | Example                  |
+/// | ---------------------- | ------------------------ |
+/// | `%DAY_NUMBER%`         | `5`                      |
+/// | `%DAY%`                | `05`                     |
+/// | `%YEAR%`               | `2024`                   |
+/// | `%ANSWER_TYPE%`        | `u64`                    |
+/// | `%TITLE%`              | `Day 5: Print Queue`     |
+/// | `%EXAMPLE_ANSWER_1%`   | `Some(143)` or `None`    |
+/// | `%EXAMPLE_ANSWER_2%`   | `Some(123)` or `None`    |
+pub struct Placeholders {
+    pub day: Day,
+    pub year: Option<u16>,
+    pub answer_type: String,
+    pub title: Option<String>,
+    pub example_answers: [Option<String>; 2],
+}
+
+impl Placeholders {
+    /// Collects the placeholder values of `day`. The title and example answers are read from the
+    /// puzzle description if it was downloaded.
+    pub fn new(day: Day, answer_type: Option<&str>) -> Self {
+        let config = config::get();
+        let puzzle = fs::read_to_string(config.paths.puzzle(day)).unwrap_or_default();
+
+        Self {
+            day,
+            year: config.year,
+            answer_type: answer_type
+                .or(config.answer_type.as_deref())
+                .unwrap_or(DEFAULT_ANSWER_TYPE)
+                .to_string(),
+            title: puzzle_title(&puzzle),
+            example_answers: example_answers(&puzzle),
+        }
+    }
+
+    pub fn render(&self, template: &str) -> String {
+        let answer =
+            |x: &Option<String>| x.as_ref().map_or("None".into(), |x| format!("Some({x})"));
+
+        template
+            .replace("%DAY_NUMBER%", &self.day.into_inner().to_string())
+            .replace("%DAY%", &self.day.to_string())
+            .replace(
+                "%YEAR%",
+                &self.year.map_or(String::new(), |x| x.to_string()),
+            )
+            .replace("%ANSWER_TYPE%", &self.answer_type)
+            .replace(
+                "%TITLE%",
+                &self
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| format!("Day {}", self.day.into_inner())),
+            )
+            .replace("%EXAMPLE_ANSWER_1%", &answer(&self.example_answers[0]))
+            .replace("%EXAMPLE_ANSWER_2%", &answer(&self.example_answers[1]))
+    }
+}
+
+/// Returns the path of the user template `name`.
+fn user_template_path(name: &str) -> String {
+    format!("{}/{name}.txt", config::get().templates)
+}
+
+/// Loads a template by name, user templates take precedence over built-in ones.
+/// A path to a file, e.g. `templates/day.txt`, is also accepted.
+pub fn load(name: &str) -> Result<String> {
+    let path = name.contains(['/', '.']).then(|| name.to_string());
+
+    for path in std::iter::once(user_template_path(name)).chain(path) {
+        match fs::read_to_string(&path) {
+            Ok(contents) => return Ok(contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e).context(format!("Failed to read template \"{path}\"")),
+        }
+    }
+
+    BUILT_IN
+        .iter()
+        .find(|t| t.name == name)
+        .map(|t| t.contents.to_string())
+        .ok_or_else(|| {
+            Error::Usage(format!(
+                "unknown template `{name}`. Run `cargo templates` to list the available templates."
+            ))
+        })
+}
+
+/// Returns the names of the user templates, sorted.
+pub fn user_templates() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(&config::get().templates) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Some(Path::new(path.file_stem()?).to_string_lossy().to_string())
+        })
+        .collect();
+
+    names.sort();
+    names
+}
+
+/// Reads the puzzle title from the `--- Day 5: Print Queue ---` heading of a puzzle description.
+fn puzzle_title(puzzle: &str) -> Option<String> {
+    puzzle.lines().find_map(|line| {
+        let title = line
+            .trim_start_matches(['#', ' ', '\\'])
+            .strip_prefix("--- ")?;
+        let title = title.strip_suffix(" ---")?.trim();
+        title.starts_with("Day ").then(|| title.to_string())
+    })
+}
+
+/// Guesses the example answer of each part: AoC emphasizes it as the last highlighted number of the part.
+fn example_answers(puzzle: &str) -> [Option<String>; 2] {
+    let (one, two) = match puzzle.find("--- Part Two ---") {
+        Some(index) => puzzle.split_at(index),
+        None => (puzzle, ""),
+    };
+
+    [last_highlighted_number(one), last_highlighted_number(two)]
+}
+
+fn last_highlighted_number(s: &str) -> Option<String> {
+    // the markdown written by aoc-cli renders `<code><em>143</em></code>` as `*143*` wrapped in backticks.
+    s.split('`')
+        .filter_map(|x| x.strip_prefix('*')?.strip_suffix('*'))
+        .rfind(|x| !x.is_empty() && x.chars().all(|c| c.is_ascii_digit() || c == '-'))
+        .map(str::to_string)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{example_answers, puzzle_title, Placeholders, BUILT_IN};
+    use crate::day;
+
+    const PUZZLE: &str = "\\--- Day 5: Print Queue ---
+----------
+
+Of the `*6*` updates, `*3*` are in order. The sum of their middle pages is `*143*`.
+
+\\--- Part Two ---
+----------
+
+After ordering, the sum is `*123*`.
+";
+
+    #[test]
+    fn reads_puzzle_title() {
+        assert_eq!(puzzle_title(PUZZLE).as_deref(), Some("Day 5: Print Queue"));
+        assert_eq!(puzzle_title("no title"), None);
+    }
+
+    #[test]
+    fn reads_example_answers() {
+        assert_eq!(
+            example_answers(PUZZLE),
+            [Some("143".into()), Some("123".into())]
+        );
+        assert_eq!(example_answers("`*x*`"), [None, None]);
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            day: day!(5),
+            year: Some(2024),
+            answer_type: "u64".into(),
+            title: None,
+            example_answers: [Some("143".into()), None],
+        };
+
+        assert_eq!(
+            placeholders.render("%DAY% %DAY_NUMBER% %YEAR% %TITLE% %ANSWER_TYPE%"),
+            "05 5 2024 Day 5 u64"
+        );
+        assert_eq!(
+            placeholders.render("%EXAMPLE_ANSWER_1% %EXAMPLE_ANSWER_2%"),
+            "Some(143) None"
+        );
+    }
+
+    #[test]
+    fn built_in_templates_use_placeholders() {
+        for template in BUILT_IN {
+            assert!(template.contents.contains("solution!(%DAY_NUMBER%"));
+            assert!(template.contents.contains("%ANSWER_TYPE%"));
+        }
+    }
+}
@@ -0,0 +1,202 @@
+/// Full-screen terminal handling over plain ANSI escape codes, used by `cargo dashboard`.
+/// Key presses are read unbuffered by switching the terminal mode with `stty`.
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+
+use crate::template::error::{Error, Result};
+
+pub const ANSI_DIM: &str = "\x1b[2m";
+pub const ANSI_NORMAL_INTENSITY: &str = "\x1b[22m";
+pub const ANSI_REVERSE: &str = "\x1b[7m";
+
+const ANSI_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+const ANSI_MAIN_SCREEN: &str = "\x1b[?1049l";
+const ANSI_HIDE_CURSOR: &str = "\x1b[?25l";
+const ANSI_SHOW_CURSOR: &str = "\x1b[?25h";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Enter,
+    CtrlC,
+    Char(char),
+    Other,
+}
+
+/// Puts the terminal into unbuffered mode and switches to the alternate screen. Both are restored on drop.
+pub struct RawTerminal {
+    saved_mode: String,
+}
+
+impl RawTerminal {
+    pub fn enter() -> Result<Self> {
+        let saved_mode = stty(&["-g"])?.trim().to_string();
+        // `-isig` delivers Ctrl-C as a key, so that the terminal is always restored.
+        stty(&["-icanon", "-echo", "-isig", "min", "1", "time", "0"])?;
+
+        print!("{ANSI_ALTERNATE_SCREEN}{ANSI_HIDE_CURSOR}");
+        io::stdout().flush().ok();
+
+        Ok(Self { saved_mode })
+    }
+
+    /// Returns the number of rows and columns of the terminal.
+    pub fn size() -> (usize, usize) {
+        stty(&["size"])
+            .ok()
+            .and_then(|size| {
+                let (rows, cols) = size.trim().split_once(' ')?;
+                Some((rows.parse().ok()?, cols.parse().ok()?))
+            })
+            .filter(|&(rows, cols)| rows > 0 && cols > 0)
+            .unwrap_or((24, 80))
+    }
+
+    /// Replaces the screen contents with `lines`.
+    pub fn draw(lines: &[String]) {
+        let mut screen = String::from("\x1b[H");
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                screen.push_str("\r\n");
+            }
+            screen.push_str(line);
+            screen.push_str("\x1b[K");
+        }
+        screen.push_str("\x1b[J");
+
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(screen.as_bytes()).ok();
+        stdout.flush().ok();
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        print!("{ANSI_SHOW_CURSOR}{ANSI_MAIN_SCREEN}");
+        io::stdout().flush().ok();
+        stty(&[&self.saved_mode]).ok();
+    }
+}
+
+fn stty(args: &[&str]) -> Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()
+        .map_err(|_| Error::MissingTool {
+            tool: "stty",
+            hint: Some("The dashboard requires a unix terminal."),
+        })?;
+
+    if !output.status.success() {
+        return Err(Error::Usage(
+            "the dashboard must be run in an interactive terminal.".into(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Reads a key press, returns `None` once the input is closed.
+pub fn read_key(input: &mut impl Read) -> Option<Key> {
+    let mut next = || {
+        let mut byte = [0];
+        input.read_exact(&mut byte).ok().map(|()| byte[0])
+    };
+
+    Some(match next()? {
+        b'\x1b' => {
+            if next()? != b'[' {
+                return Some(Key::Other);
+            }
+            match next()? {
+                b'A' => Key::Up,
+                b'B' => Key::Down,
+                b'C' => Key::Right,
+                b'D' => Key::Left,
+                code @ (b'5' | b'6') => {
+                    if next()? != b'~' {
+                        return Some(Key::Other);
+                    }
+                    if code == b'5' {
+                        Key::PageUp
+                    } else {
+                        Key::PageDown
+                    }
+                }
+                _ => Key::Other,
+            }
+        }
+        b'\r' | b'\n' => Key::Enter,
+        3 => Key::CtrlC,
+        byte if byte.is_ascii() => Key::Char(byte as char),
+        _ => Key::Other,
+    })
+}
+
+/// Prepares a line of child output for the screen: keeps the text after the last carriage return and strips
+/// escape codes, which would break the layout.
+pub fn sanitize(line: &str) -> String {
+    let line = line.rsplit('\r').next().unwrap_or_default();
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip a control sequence up to its final byte, e.g. `\x1b[1m`.
+            if chars.next() == Some('[') {
+                chars.by_ref().find(|c| ('\x40'..='\x7e').contains(c));
+            }
+        } else if c == '\t' {
+            result.push_str("    ");
+        } else if !c.is_control() {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Pads or truncates `s` to `width` characters.
+pub fn fit(s: &str, width: usize) -> String {
+    format!("{:<width$.width$}", s)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fit, read_key, sanitize, Key};
+
+    #[test]
+    fn reads_keys() {
+        let mut input: &[u8] = b"s\x1b[A\x1b[D\x1b[6~\n\x03";
+        let keys: Vec<_> = std::iter::from_fn(|| read_key(&mut input)).collect();
+
+        assert_eq!(
+            keys,
+            vec![
+                Key::Char('s'),
+                Key::Up,
+                Key::Left,
+                Key::PageDown,
+                Key::Enter,
+                Key::CtrlC
+            ]
+        );
+    }
+
+    #[test]
+    fn sanitizes_output() {
+        assert_eq!(
+            sanitize("Part 1: \x1b[1m2\x1b[0m\rPart 1: \x1b[1m2\x1b[0m (1.6µs)"),
+            "Part 1: 2 (1.6µs)"
+        );
+        assert_eq!(sanitize("a\tb\x07"), "a    b");
+        assert_eq!(fit("Day 05", 4), "Day ");
+        assert_eq!(fit("✓", 3), "✓  ");
+    }
+}
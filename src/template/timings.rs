@@ -1,9 +1,9 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::config;
+use crate::template::error::{Context, Error, Result};
+use crate::template::{machine::Machine, Day};
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -12,33 +12,126 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub parse: Option<String>,
+    pub memory: Option<String>,
+    pub samples: Option<u64>,
+    pub hash: Option<String>,
 }
 
-/// Represents benchmark times for a set of days.
+/// Represents benchmark times for a set of days, measured on a single machine.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    pub machine: Option<Machine>,
 }
 
-impl Timings {
+/// All stored sets of timings, one per machine, and the table that is rendered in the readme.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct TimingsStore {
+    pub sets: Vec<Timings>,
+    pub table: Option<Timings>,
+}
+
+impl TimingsStore {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self) -> Result<()> {
+        let path = &config::get().paths.timings;
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path).context(format!("Failed to create \"{path}\""))?;
         json.format_to(&mut file)
+            .context(format!("Failed to write \"{path}\""))
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        fs::read_to_string(&config::get().paths.timings)
             .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
+            .and_then(TimingsStore::try_from)
             .unwrap_or_default()
     }
 
+    /// Returns the timings measured on `machine`, or an empty set for it.
+    pub fn get(&self, machine: Option<&Machine>) -> Timings {
+        self.sets
+            .iter()
+            .find(|t| t.machine.as_ref() == machine)
+            .cloned()
+            .unwrap_or_else(|| Timings {
+                data: vec![],
+                machine: machine.cloned(),
+            })
+    }
+
+    /// Inserts a set of timings, replacing the stored set of the same machine.
+    pub fn insert(&mut self, timings: Timings) {
+        self.sets.retain(|t| t.machine != timings.machine);
+        self.sets.push(timings);
+    }
+
+    /// Returns the timings rendered in the readme. Files written before the table was stored separately
+    /// hold a single set of timings without a machine, which is what their readme shows.
+    pub fn table(&self) -> Timings {
+        self.table.clone().unwrap_or_else(|| self.get(None))
+    }
+
+    /// Stores new timings in the set of their machine and merges them into the readme table, which is returned.
+    /// Fails if the table holds timings of another machine, unless `merge_machines` is set.
+    pub fn record(&mut self, timings: &Timings, merge_machines: bool) -> Result<Timings> {
+        let table = if merge_machines {
+            // newer sets take precedence.
+            self.sets
+                .iter()
+                .fold(self.table(), |acc, t| acc.merge_mixed(t))
+                .merge_mixed(timings)
+        } else {
+            self.table().merge(timings)?
+        };
+
+        let set = self.get(timings.machine.as_ref()).merge_mixed(timings);
+        self.insert(set);
+        // the timings of a legacy file live on in the table.
+        self.sets.retain(|t| t.machine.is_some());
+        self.table = Some(table.clone());
+
+        Ok(table)
+    }
+}
+
+/// The error returned when merging timings that were measured on different machines.
+fn mixed_machines_error(stored: Option<&Machine>, new: Option<&Machine>) -> Error {
+    let describe = |m: Option<&Machine>| {
+        m.map_or_else(|| "several or unknown machines".into(), ToString::to_string)
+    };
+
+    Error::Usage(format!(
+        "refusing to merge timings of {} with timings of {}. Pass `--merge-machines` to merge anyway.",
+        describe(stored),
+        describe(new)
+    ))
+}
+
+impl Timings {
     /// Merge two sets of timings, overwriting `self` with `other` if present.
-    pub fn merge(&self, new: &Self) -> Self {
+    /// Fails if both sets contain timings and were measured on different machines.
+    pub fn merge(&self, new: &Self) -> Result<Self> {
+        if self.machine != new.machine && !self.data.is_empty() && !new.data.is_empty() {
+            return Err(mixed_machines_error(
+                self.machine.as_ref(),
+                new.machine.as_ref(),
+            ));
+        }
+
+        Ok(Timings {
+            machine: new.machine.clone(),
+            ..self.merge_mixed(new)
+        })
+    }
+
+    /// Merge two sets of timings regardless of the machines they were measured on.
+    /// The merged set is only attributed to a machine if both sets agree on it.
+    pub fn merge_mixed(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
@@ -51,8 +144,15 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|t| t.day);
+
+        let machine = if self.machine == new.machine {
+            new.machine.clone()
+        } else {
+            None
+        };
+
+        Timings { data, machine }
     }
 
     /// Sum up total duration of timings as millis.
@@ -65,6 +165,138 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Returns why a day needs to be benched again given its current content hash, `None` if it is up to date.
+    pub fn stale_reason(&self, day: Day, hash: &str) -> Option<StaleReason> {
+        let Some(timing) = self.data.iter().find(|t| t.day == day) else {
+            return Some(StaleReason::NotBenched);
+        };
+
+        if !self.is_day_complete(day) {
+            return Some(StaleReason::Incomplete);
+        }
+
+        match &timing.hash {
+            None => Some(StaleReason::NoHash),
+            Some(stored) if stored != hash => Some(StaleReason::Changed),
+            Some(_) => None,
+        }
+    }
+}
+
+/// The reason a stored timing is no longer up to date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StaleReason {
+    NotBenched,
+    Incomplete,
+    NoHash,
+    Changed,
+}
+
+impl Display for StaleReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StaleReason::NotBenched => write!(f, "not benched yet"),
+            StaleReason::Incomplete => write!(f, "missing timings for a part"),
+            StaleReason::NoHash => write!(f, "stored timing has no content hash"),
+            StaleReason::Changed => write!(f, "solution, library or input changed"),
+        }
+    }
+}
+
+/// Parses a duration as printed by the runner (e.g. `74.13ns`, `1.2ms`, `2s`) into nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+        s.split(postfix).next()?.trim().parse().ok()
+    }
+
+    match s.trim() {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+const MEMORY_UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+/// Formats a number of bytes as printed by the runner, e.g. `512 B` or `1.3 MiB`.
+pub fn format_memory(bytes: u64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024_f64 && unit < MEMORY_UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", MEMORY_UNITS[unit])
+    }
+}
+
+/// Parses an amount of memory as printed by the runner (e.g. `512 B`, `1.3 MiB`) into bytes.
+pub fn parse_memory(s: &str) -> Option<u64> {
+    let (value, unit) = s.trim().split_once(' ')?;
+    let value: f64 = value.parse().ok()?;
+    let exponent = MEMORY_UNITS.iter().position(|x| *x == unit.trim())?;
+    let factor = 1024_f64.powi(i32::try_from(exponent).ok()?);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some((value * factor) as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<TimingsStore> for JsonValue {
+    fn from(value: TimingsStore) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "machines".into(),
+            JsonValue::Array(value.sets.into_iter().map(JsonValue::from).collect()),
+        );
+
+        if let Some(table) = value.table {
+            map.insert("table".into(), JsonValue::from(table));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for TimingsStore {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let map = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        // files written before timings were tagged with machines hold a single set.
+        let Some(sets) = map.get("machines") else {
+            return Ok(TimingsStore {
+                sets: vec![Timings::try_from(&json)?],
+                table: None,
+            });
+        };
+
+        Ok(TimingsStore {
+            sets: sets
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.machines` to be an array.")?
+                .iter()
+                .map(Timings::try_from)
+                .collect::<Result<_, _>>()?,
+            table: map.get("table").map(Timings::try_from).transpose()?,
+        })
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -78,6 +310,10 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        if let Some(machine) = &value.machine {
+            map.insert("machine".into(), JsonValue::from(machine));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -87,20 +323,32 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
 
-        let json_data = json
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
+
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
+        let map = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = map
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        let machine = map.get("machine").map(Machine::try_from).transpose()?;
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            machine,
         })
     }
 }
@@ -133,6 +381,23 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::String(memory.clone()));
+        }
+
+        if let Some(hash) = &value.hash {
+            map.insert("hash".into(), JsonValue::String(hash.clone()));
+        }
+
+        if let Some(samples) = value.samples {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("samples".into(), JsonValue::Number(samples as f64));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +431,26 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // optional keys, absent in timings stored by earlier versions.
+        let parse = json.get("parse").and_then(|v| v.get::<String>()).cloned();
+        let memory = json.get("memory").and_then(|v| v.get::<String>()).cloned();
+        let hash = json.get("hash").and_then(|v| v.get::<String>()).cloned();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64);
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            parse,
+            memory,
+            samples,
+            hash,
         })
     }
 }
@@ -179,10 +459,20 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::{day, template::machine::Machine};
 
     use super::{Timing, Timings};
 
+    fn get_mock_machine(cpu: &str) -> Machine {
+        Machine {
+            cpu: cpu.into(),
+            cores: 8,
+            rustc: "rustc 1.83.0".into(),
+            profile: "release".into(),
+            threads: 8,
+        }
+    }
+
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
@@ -191,20 +481,33 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
                 },
             ],
+            machine: None,
         }
     }
 
@@ -282,10 +585,15 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
                 }],
+                machine: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -296,10 +604,15 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
                 }],
+                machine: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,10 +623,75 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
                 }],
+                machine: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod stale_reason {
+        use crate::{
+            day,
+            template::timings::{StaleReason, Timing, Timings},
+        };
+
+        fn get_timings(hash: Option<&str>, part_2: Option<&str>) -> Timings {
+            Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: Some("1ms".into()),
+                    part_2: part_2.map(Into::into),
+                    total_nanos: 3_000_000_000_f64,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: hash.map(Into::into),
+                }],
+                machine: None,
+            }
+        }
+
+        #[test]
+        fn handles_unchanged_days() {
+            let timings = get_timings(Some("abc"), Some("2ms"));
+            assert_eq!(timings.stale_reason(day!(1), "abc"), None);
+        }
+
+        #[test]
+        fn handles_changed_days() {
+            let timings = get_timings(Some("abc"), Some("2ms"));
+            assert_eq!(
+                timings.stale_reason(day!(1), "def"),
+                Some(StaleReason::Changed)
+            );
+        }
+
+        #[test]
+        fn handles_days_without_hash() {
+            let timings = get_timings(None, Some("2ms"));
+            assert_eq!(
+                timings.stale_reason(day!(1), "abc"),
+                Some(StaleReason::NoHash)
+            );
+        }
+
+        #[test]
+        fn handles_incomplete_and_missing_days() {
+            let timings = get_timings(Some("abc"), None);
+            assert_eq!(
+                timings.stale_reason(day!(1), "abc"),
+                Some(StaleReason::Incomplete)
+            );
+            assert_eq!(
+                timings.stale_reason(day!(2), "abc"),
+                Some(StaleReason::NotBenched)
+            );
         }
     }
 
@@ -323,7 +701,7 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
-        use super::get_mock_timings;
+        use super::{get_mock_machine, get_mock_timings};
 
         #[test]
         fn handles_disjunct_timings() {
@@ -334,9 +712,14 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
                 }],
+                machine: None,
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other).unwrap();
             assert_eq!(merged.data.len(), 4);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
@@ -354,9 +737,14 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    parse: None,
+                    memory: None,
+                    samples: None,
+                    hash: None,
                 }],
+                machine: None,
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other).unwrap();
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
@@ -369,7 +757,7 @@ mod tests {
         fn handles_empty_timings() {
             let timings = Timings::default();
             let other = get_mock_timings();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other).unwrap();
             assert_eq!(merged.data.len(), 3);
         }
 
@@ -377,8 +765,175 @@ mod tests {
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
             let other = Timings::default();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other).unwrap();
+            assert_eq!(merged.data.len(), 3);
+        }
+
+        #[test]
+        fn refuses_mixed_machines() {
+            let mut timings = get_mock_timings();
+            timings.machine = Some(get_mock_machine("A"));
+            let mut other = get_mock_timings();
+            other.machine = Some(get_mock_machine("B"));
+
+            assert!(timings.merge(&other).is_err());
+
+            let merged = timings.merge_mixed(&other);
             assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.machine, None);
+        }
+
+        #[test]
+        fn keeps_machine_of_empty_timings() {
+            let timings = Timings {
+                data: vec![],
+                machine: Some(get_mock_machine("A")),
+            };
+            let mut other = get_mock_timings();
+            other.machine = Some(get_mock_machine("B"));
+
+            let merged = timings.merge(&other).unwrap();
+            assert_eq!(merged.machine, Some(get_mock_machine("B")));
+        }
+    }
+
+    mod store {
+        use crate::template::error::Error;
+        use crate::template::timings::{Timings, TimingsStore};
+
+        use super::{get_mock_machine, get_mock_timings};
+
+        fn measured_on(cpu: &str) -> Timings {
+            Timings {
+                machine: Some(get_mock_machine(cpu)),
+                ..get_mock_timings()
+            }
+        }
+
+        #[test]
+        fn handles_legacy_files() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let store = TimingsStore::try_from(json).unwrap();
+            assert_eq!(store.sets.len(), 1);
+            assert_eq!(store.sets[0].machine, None);
+            assert_eq!(store.get(None).data.len(), 1);
+        }
+
+        #[test]
+        fn stores_one_set_per_machine() {
+            let mut store = TimingsStore::default();
+            let machine_a = get_mock_machine("A");
+
+            store.insert(Timings {
+                machine: Some(machine_a.clone()),
+                ..get_mock_timings()
+            });
+            store.insert(Timings {
+                machine: Some(get_mock_machine("B")),
+                ..get_mock_timings()
+            });
+            store.insert(Timings {
+                data: vec![],
+                machine: Some(machine_a.clone()),
+            });
+
+            assert_eq!(store.sets.len(), 2);
+            assert_eq!(store.get(Some(&machine_a)).data.len(), 0);
+            assert_eq!(store.get(Some(&get_mock_machine("B"))).data.len(), 3);
+            assert_eq!(store.get(Some(&get_mock_machine("C"))).data.len(), 0);
+        }
+
+        #[test]
+        fn roundtrips_json() {
+            let mut store = TimingsStore::default();
+            store.insert(Timings {
+                machine: Some(get_mock_machine("A")),
+                ..get_mock_timings()
+            });
+
+            let json = tinyjson::JsonValue::from(store).stringify().unwrap();
+            let store = TimingsStore::try_from(json).unwrap();
+            assert_eq!(store.sets.len(), 1);
+            assert_eq!(store.sets[0].machine, Some(get_mock_machine("A")));
+            assert_eq!(store.sets[0].data.len(), 3);
+            assert!(store.table.is_none());
+        }
+
+        #[test]
+        fn records_timings_of_one_machine() {
+            let mut store = TimingsStore::default();
+            store.record(&measured_on("A"), false).unwrap();
+
+            let mut update = measured_on("A");
+            update.data.truncate(1);
+            update.data[0].part_1 = Some("1ms".into());
+            let table = store.record(&update, false).unwrap();
+
+            assert_eq!(table.machine, Some(get_mock_machine("A")));
+            assert_eq!(table.data.len(), 3);
+            assert_eq!(table.data[0].part_1.as_deref(), Some("1ms"));
+            assert_eq!(store.sets.len(), 1);
+            assert_eq!(store.get(Some(&get_mock_machine("A"))).data.len(), 3);
+
+            let json = tinyjson::JsonValue::from(store).stringify().unwrap();
+            let store = TimingsStore::try_from(json).unwrap();
+            assert_eq!(store.table().machine, Some(get_mock_machine("A")));
+            assert_eq!(store.table().data[0].part_1.as_deref(), Some("1ms"));
+        }
+
+        #[test]
+        fn refuses_to_mix_machines_in_the_table() {
+            let mut store = TimingsStore::default();
+            store.record(&measured_on("A"), false).unwrap();
+
+            assert!(matches!(
+                store.record(&measured_on("B"), false),
+                Err(Error::Usage(_))
+            ));
+            assert_eq!(store.sets.len(), 1);
+
+            let table = store.record(&measured_on("B"), true).unwrap();
+            assert_eq!(table.machine, None);
+            assert_eq!(store.sets.len(), 2);
+            assert!(store.sets.iter().all(|t| t.machine.is_some()));
+
+            // the table now holds timings of several machines.
+            assert!(store.record(&measured_on("A"), false).is_err());
+        }
+
+        #[test]
+        fn merges_legacy_files_into_the_table() {
+            let json = r#"{ "data": [{ "day": "09", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let mut store = TimingsStore::try_from(json).unwrap();
+            assert_eq!(store.table().data.len(), 1);
+
+            assert!(store.record(&measured_on("A"), false).is_err());
+
+            let table = store.record(&measured_on("A"), true).unwrap();
+            assert_eq!(table.data.len(), 4);
+            assert_eq!(table.machine, None);
+            assert_eq!(store.sets.len(), 1);
+            assert_eq!(store.sets[0].machine, Some(get_mock_machine("A")));
+        }
+    }
+
+    mod memory {
+        use super::super::{format_memory, parse_memory};
+
+        #[test]
+        fn formats_memory() {
+            assert_eq!(format_memory(512), "512 B");
+            assert_eq!(format_memory(1536), "1.5 KiB");
+            assert_eq!(format_memory(1_363_149), "1.3 MiB");
+        }
+
+        #[test]
+        fn parses_memory() {
+            assert_eq!(parse_memory("512 B"), Some(512));
+            assert_eq!(parse_memory("1.5 KiB"), Some(1536));
+            assert_eq!(parse_memory(&format_memory(5 << 30)), Some(5 << 30));
+            assert_eq!(parse_memory("1.5 KB"), None);
+            assert_eq!(parse_memory("-"), None);
         }
     }
 }
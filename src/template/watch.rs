@@ -0,0 +1,270 @@
+/// Polls the sources and data files of a day for changes, used by `cargo solve --watch`.
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::config;
+use crate::template::error::Result;
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Modification time and size of each watched file.
+type Snapshot = HashMap<PathBuf, (SystemTime, u64)>;
+
+/// Runs `run` once and then again whenever a watched file of `day` changes, until the process is interrupted.
+/// Watched are the solution of the day, the library modules in `src`, `Cargo.toml` and the day's input and examples.
+pub fn watch(day: Day, mut run: impl FnMut() -> Result<()>) -> Result<()> {
+    let mut snapshot = take_snapshot(day);
+    let mut changes = vec![];
+
+    loop {
+        print!("{ANSI_CLEAR_SCREEN}");
+        match changes.as_slice() {
+            [] => println!("{ANSI_BOLD}Watching day {day}{ANSI_RESET}"),
+            changes => println!(
+                "{ANSI_BOLD}Watching day {day}{ANSI_RESET} {ANSI_ITALIC}changed: {}{ANSI_RESET}",
+                changes
+                    .iter()
+                    .map(|path: &PathBuf| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+        println!();
+
+        run()?;
+
+        println!("\n{ANSI_ITALIC}Waiting for changes, press Ctrl-C to stop.{ANSI_RESET}");
+
+        let next = wait_for_change(day, &snapshot);
+        changes = changed_files(&snapshot, &next);
+        snapshot = next;
+    }
+}
+
+/// Polls until the snapshot differs from `snapshot` and then settles, editors often write a file in several steps.
+fn wait_for_change(day: Day, snapshot: &Snapshot) -> Snapshot {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let mut next = take_snapshot(day);
+        if next == *snapshot {
+            continue;
+        }
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let settled = take_snapshot(day);
+            if settled == next {
+                return next;
+            }
+            next = settled;
+        }
+    }
+}
+
+fn take_snapshot(day: Day) -> Snapshot {
+    watched_files(day)
+        .into_iter()
+        .filter_map(|path| {
+            let metadata = fs::metadata(&path).ok()?;
+            Some((path, (metadata.modified().ok()?, metadata.len())))
+        })
+        .collect()
+}
+
+fn watched_files(day: Day) -> Vec<PathBuf> {
+    let paths = &config::get().paths;
+    let mut files = vec![PathBuf::from("Cargo.toml"), PathBuf::from(paths.input(day))];
+
+    collect_sources(Path::new("src"), day, &mut files);
+
+    if let Ok(entries) = fs::read_dir(&paths.examples) {
+        files.extend(
+            entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| is_example_of(path, day)),
+        );
+    }
+
+    files
+}
+
+/// Collects the `.rs` files below `dir`, skipping the solutions of other days.
+fn collect_sources(dir: &Path, day: Day, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+        if path.is_dir() {
+            collect_sources(&path, day, files);
+        } else if path.extension().is_some_and(|x| x == "rs") && !is_other_solution(&path, day) {
+            files.push(path);
+        }
+    }
+}
+
+fn is_other_solution(path: &Path, day: Day) -> bool {
+    path.parent().is_some_and(|dir| dir.ends_with("src/bin"))
+        && path.file_stem() != Some(day.to_string().as_ref())
+}
+
+/// Whether `path` is an example of `day`, e.g. `05.txt` or `05-2.txt`.
+fn is_example_of(path: &Path, day: Day) -> bool {
+    let Some(name) = path.file_name().and_then(|x| x.to_str()) else {
+        return false;
+    };
+
+    name.strip_prefix(&day.to_string())
+        .and_then(|rest| rest.strip_suffix(".txt"))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+}
+
+/// Returns the files that were added, removed or modified between two snapshots, sorted.
+fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = before
+        .iter()
+        .filter(|(path, stamp)| after.get(*path) != Some(stamp))
+        .map(|(path, _)| path.clone())
+        .chain(
+            after
+                .keys()
+                .filter(|path| !before.contains_key(*path))
+                .cloned(),
+        )
+        .collect();
+
+    changed.sort();
+    changed
+}
+
+/// Result of a single test in the output of `cargo test`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TestResult {
+    pub name: String,
+    pub passed: bool,
+    /// Panic message of a failed test.
+    pub message: Vec<String>,
+}
+
+/// Parses the results of the test harness output, e.g. `test tests::part_one::example_1 ... ok`.
+pub fn parse_test_results(stdout: &[String]) -> Vec<TestResult> {
+    let mut results: Vec<TestResult> = stdout
+        .iter()
+        .filter_map(|line| {
+            let (name, outcome) = line.strip_prefix("test ")?.rsplit_once(" ... ")?;
+            let passed = match outcome {
+                "ok" => true,
+                "FAILED" => false,
+                _ => return None,
+            };
+            Some(TestResult {
+                name: name.to_string(),
+                passed,
+                message: vec![],
+            })
+        })
+        .collect();
+
+    // failures are reported in `---- <name> stdout ----` sections after all tests ran.
+    let mut current = None;
+    for line in stdout {
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|x| x.strip_suffix(" stdout ----"))
+        {
+            current = results.iter().position(|result| result.name == name);
+        } else if line == "failures:" || line.starts_with("test result:") {
+            current = None;
+        } else if let Some(index) = current {
+            if !line.is_empty() && !line.starts_with("note: run with `RUST_BACKTRACE") {
+                results[index].message.push(line.clone());
+            }
+        }
+    }
+
+    results
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, SystemTime};
+
+    use super::{changed_files, is_example_of, is_other_solution, parse_test_results};
+    use crate::day;
+
+    #[test]
+    fn matches_watched_files() {
+        assert!(is_example_of(Path::new("data/examples/05.txt"), day!(5)));
+        assert!(is_example_of(Path::new("data/examples/05-2.txt"), day!(5)));
+        assert!(!is_example_of(Path::new("data/examples/15.txt"), day!(5)));
+        assert!(!is_example_of(Path::new("data/examples/050.txt"), day!(5)));
+
+        assert!(is_other_solution(Path::new("src/bin/06.rs"), day!(5)));
+        assert!(!is_other_solution(Path::new("src/bin/05.rs"), day!(5)));
+        assert!(!is_other_solution(Path::new("src/lib.rs"), day!(5)));
+    }
+
+    #[test]
+    fn finds_changed_files() {
+        let t = SystemTime::UNIX_EPOCH;
+        let before = HashMap::from([
+            (PathBuf::from("a"), (t, 1)),
+            (PathBuf::from("b"), (t, 1)),
+            (PathBuf::from("c"), (t, 1)),
+        ]);
+        let after = HashMap::from([
+            (PathBuf::from("a"), (t, 1)),
+            (PathBuf::from("b"), (t + Duration::from_secs(1), 1)),
+            (PathBuf::from("d"), (t, 1)),
+        ]);
+
+        assert_eq!(
+            changed_files(&before, &after),
+            vec![PathBuf::from("b"), PathBuf::from("c"), PathBuf::from("d")]
+        );
+    }
+
+    #[test]
+    fn parses_test_results() {
+        let stdout: Vec<String> = "running 2 tests
+test tests::part_one::example_1 ... ok
+test tests::part_one::example_2 ... FAILED
+
+failures:
+
+---- tests::part_one::example_2 stdout ----
+
+thread 'tests::part_one::example_2' panicked at src/bin/05.rs:15:5:
+assertion `left == right` failed: example file \"data/examples/05-2.txt\"
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+failures:
+    tests::part_one::example_2
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out"
+            .lines()
+            .map(str::to_string)
+            .collect();
+
+        let results = parse_test_results(&stdout);
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].passed);
+        assert!(results[0].message.is_empty());
+        assert!(!results[1].passed);
+        assert_eq!(
+            results[1].message,
+            vec![
+                "thread 'tests::part_one::example_2' panicked at src/bin/05.rs:15:5:",
+                "assertion `left == right` failed: example file \"data/examples/05-2.txt\"",
+            ]
+        );
+    }
+}
@@ -0,0 +1,90 @@
+/// Wrapper around `git worktree` that checks out a revision into a temporary directory.
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use crate::template::config;
+use crate::template::error::{Context, Error, Result};
+
+/// A detached checkout of a git revision. The checkout is removed again when this value is dropped.
+pub struct Worktree {
+    pub path: PathBuf,
+    pub label: String,
+}
+
+impl Worktree {
+    /// Checks out `rev` into a temporary directory and copies the current puzzle inputs into it,
+    /// so that both trees are benched against the same data even though inputs are usually not committed.
+    pub fn checkout(rev: &str) -> Result<Self> {
+        let label = call_git(&["rev-parse", "--short", &format!("{rev}^{{commit}}")])?;
+        let path = env::temp_dir().join(format!("aoc-rev-{}-{label}", std::process::id()));
+
+        call_git(&[
+            "worktree",
+            "add",
+            "--detach",
+            &path.to_string_lossy(),
+            &label,
+        ])?;
+
+        let worktree = Worktree { path, label };
+        worktree.copy_inputs()?;
+        Ok(worktree)
+    }
+
+    fn copy_inputs(&self) -> Result<()> {
+        let inputs = &config::get().paths.inputs;
+        let target_dir = self.path.join(inputs);
+        fs::create_dir_all(&target_dir).context("Failed to create inputs of the worktree")?;
+
+        let entries = fs::read_dir(inputs).context(format!("Failed to read \"{inputs}\""))?;
+
+        for entry in entries {
+            let entry = entry.context(format!("Failed to read \"{inputs}\""))?;
+            if entry.path().extension().is_some_and(|ext| ext == "txt") {
+                fs::copy(entry.path(), target_dir.join(entry.file_name()))
+                    .context("Failed to copy inputs into the worktree")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        if let Err(e) = call_git(&[
+            "worktree",
+            "remove",
+            "--force",
+            &self.path.to_string_lossy(),
+        ]) {
+            eprintln!(
+                "Failed to remove worktree \"{}\": {e}",
+                self.path.to_string_lossy()
+            );
+        }
+    }
+}
+
+fn call_git(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|_| Error::MissingTool {
+            tool: "git",
+            hint: None,
+        })?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(Error::Other(format!(
+            "git exited with a non-zero status: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
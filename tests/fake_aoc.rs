@@ -0,0 +1,151 @@
+//! Runs `cargo download` and `cargo read` against a local stand-in of adventofcode.com and a stand-in aoc-cli,
+//! so the parsing of real aoc-cli output is covered by the unit tests of `aoc_cli` instead.
+#![cfg(all(unix, feature = "test_lib"))]
+
+use std::process::Output;
+
+use advent_of_code::template::fake_aoc::{FakeAoc, Project, Scenario};
+
+const SESSION: &str = "53616c7465645f5f";
+const INPUT: &str = "3   4\n4   3\n2   5\n";
+
+fn scenario() -> Scenario {
+    Scenario::new(2024)
+        .session(SESSION)
+        .puzzle(
+            1,
+            "Historian Hysteria",
+            "What is the total distance between your lists?",
+            Some("What is their similarity score?"),
+        )
+        .input(1, INPUT)
+        .puzzle(2, "Red-Nosed Reports", "How many reports are safe?", None)
+        .input(2, "7 6 4 2 1\n")
+}
+
+fn run(project: &Project, args: &[&str]) -> Output {
+    project
+        .command(env!("CARGO_BIN_EXE_advent_of_code"))
+        .args(args)
+        .env("ADVENT_OF_CODE_SESSION", SESSION)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn downloads_input_and_puzzle() {
+    let server = FakeAoc::start(scenario());
+    let project = server.project("year = 2024\n");
+
+    let output = run(&project, &["download", "1"]);
+    assert!(output.status.success(), "{output:?}");
+
+    assert_eq!(project.read("data/inputs/01.txt").as_deref(), Some(INPUT));
+    let puzzle = project.read("data/puzzles/01.md").unwrap();
+    assert!(puzzle.contains("\\--- Day 1: Historian Hysteria ---"));
+    assert!(puzzle.contains("\\--- Part Two ---"));
+
+    let requests = server.requests();
+    let input = requests
+        .iter()
+        .find(|x| x.path == "/2024/day/1/input")
+        .unwrap();
+    assert_eq!(input.session.as_deref(), Some(SESSION));
+}
+
+#[test]
+fn passes_configured_session() {
+    let server = FakeAoc::start(scenario());
+    let project = server.project("year = 2024\n\n[session]\nenv = \"AOC_TEST_TOKEN\"\n");
+
+    let output = project
+        .command(env!("CARGO_BIN_EXE_advent_of_code"))
+        .args(["download", "1"])
+        .env("AOC_TEST_TOKEN", SESSION)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+
+    // the variable is not set.
+    let output = run(&project, &["download", "1"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn reports_failed_downloads() {
+    let server = FakeAoc::start(scenario());
+    let project = server.project("year = 2024\n");
+
+    let output = project
+        .command(env!("CARGO_BIN_EXE_advent_of_code"))
+        .args(["download", "1"])
+        .env("ADVENT_OF_CODE_SESSION", "expired")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(4));
+    assert_eq!(project.read("data/inputs/01.txt"), None);
+
+    let output = run(&project, &["download", "3"]);
+    assert_eq!(output.status.code(), Some(4));
+}
+
+#[test]
+fn resumes_bulk_downloads() {
+    let server = FakeAoc::start(scenario());
+    let project = server.project("year = 2024\ncontact = \"me@example.com\"\n");
+    project.write("data/inputs/01.txt", INPUT);
+
+    // day 1 is skipped, so day 2 is downloaded without waiting.
+    let output = run(&project, &["download", "1-2"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Downloaded 1 day(s) (02)"));
+    assert!(stdout.contains("Skipped 1 day(s) with an existing input (01)"));
+    let requests = server.requests();
+    assert!(requests.iter().all(|x| !x.path.contains("/day/1")));
+
+    // puzzles and inputs of bulk downloads are requested with the template's User-Agent.
+    for path in ["/2024/day/2", "/2024/day/2/input"] {
+        let request = requests.iter().find(|x| x.path == path).unwrap();
+        assert_eq!(request.session.as_deref(), Some(SESSION));
+        assert_eq!(
+            request.user_agent.as_deref(),
+            Some("github.com/fspoettel/advent-of-code-rust by me@example.com")
+        );
+    }
+    assert_eq!(
+        project.read("data/inputs/02.txt").as_deref(),
+        Some("7 6 4 2 1\n")
+    );
+    assert_eq!(
+        project.read("data/puzzles/02.md").as_deref(),
+        Some("\\--- Day 2: Red-Nosed Reports ---\n----------\n\nHow many reports are safe?\n")
+    );
+}
+
+#[test]
+fn reads_cached_and_refreshed_puzzles() {
+    let server = FakeAoc::start(scenario());
+    let project = server.project("year = 2024\n");
+    project.write(
+        "data/puzzles/01.md",
+        "\\--- Day 1: Historian Hysteria ---\n----------\n\nCached part one.\n",
+    );
+
+    let output = run(&project, &["read", "1"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Cached part one."));
+    assert!(server.requests().is_empty());
+
+    let output = run(&project, &["read", "1", "--part", "2"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = run(&project, &["read", "1", "--part", "2", "--refresh"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--- Part Two ---"));
+    assert!(stdout.contains("What is their similarity score?"));
+    assert!(!stdout.contains("total distance"));
+    assert_eq!(project.read("data/inputs/01.txt"), None);
+}
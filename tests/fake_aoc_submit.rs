@@ -0,0 +1,59 @@
+//! Submits answers via `aoc_cli::submit` against a local stand-in of adventofcode.com and a stand-in aoc-cli,
+//! which echoes the responses of the stand-in site.
+//!
+//! `aoc_cli` looks up aoc-cli on the `PATH` of this process, so the environment is changed once in a single test.
+#![cfg(all(unix, feature = "test_lib"))]
+
+use std::env;
+
+use advent_of_code::template::aoc_cli;
+use advent_of_code::template::error::Error;
+use advent_of_code::template::fake_aoc::{FakeAoc, Scenario, Verdict};
+use advent_of_code::template::Day;
+
+const SESSION: &str = "53616c7465645f5f";
+
+#[test]
+fn submits_answers() {
+    let server = FakeAoc::start(Scenario::new(2024).session(SESSION).answers(
+        1,
+        1,
+        [
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Wrong,
+            Verdict::RateLimited { wait: 42 },
+            Verdict::Correct,
+        ],
+    ));
+    let project = server.project("");
+    env::set_var("PATH", project.path_var());
+    env::set_var("ADVENT_OF_CODE_SESSION", SESSION);
+
+    let day = Day::new(1).unwrap();
+    for answer in ["3000", "10", "11"] {
+        assert!(matches!(
+            aoc_cli::submit(day, 1, answer),
+            Err(Error::WrongAnswer { part: 1, .. })
+        ));
+    }
+    assert!(matches!(
+        aoc_cli::submit(day, 1, "12"),
+        Err(Error::Network(_))
+    ));
+
+    let output = aoc_cli::submit(day, 1, "11").unwrap();
+    assert!(String::from_utf8_lossy(&output.stdout).contains("That's the right answer!"));
+
+    // nothing is queued for part two.
+    let output = aoc_cli::submit(day, 2, "31").unwrap();
+    assert!(String::from_utf8_lossy(&output.stdout).contains("right level"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 6);
+    assert!(requests.iter().all(|x| x.method == "POST"
+        && x.path == "/2024/day/1/answer"
+        && x.session.as_deref() == Some(SESSION)));
+    assert_eq!(requests[0].body, "level=1&answer=3000");
+    assert_eq!(requests[5].body, "level=2&answer=31");
+}